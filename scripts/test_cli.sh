@@ -45,6 +45,187 @@ section "Truncate Command"
 run 'cargo run --quiet -- truncate --width 5 "Hello 👋 世界" -v'
 echo -e "✅ Truncation by width succeeded"
 
+section "Terminal Width (--width auto / -N)"
+run 'COLUMNS=12 cargo run --quiet -- truncate --width auto "Hello 👋 世界 and more"'
+run 'COLUMNS=12 cargo run --quiet -- split --width -4 "Hello 👋 世界 and more"'
+run 'COLUMNS= cargo run --quiet -- truncate -w -70 "0123456789abcdefghij"'   # falls back to 80
+run 'cargo run --quiet -- truncate "Hello" 2>&1 | head -n 1'                  # ⚠️ Required when piped
+echo -e "✅ Width taken from the terminal"
+
+section "Emulator Profiles"
+run 'cargo run --quiet -- widths -p xterm "→👩‍👩‍👧❤️🇯🇵"'
+run 'cargo run --quiet -- widths -p kitty "→👩‍👩‍👧❤️🇯🇵"'
+run 'env -u TMUX TERM_PROGRAM=WezTerm cargo run --quiet -- width -p auto "👩‍👩‍👧"'
+echo -e "✅ Emulator profiles applied"
+
+section "Custom Policies"
+CFG_DIR=$(mktemp -d)
+mkdir -p "$CFG_DIR/runefix"
+printf '[policy.demo]\nbase = xterm\nambiguous = 2\nU+E0B0 = 2\n' > "$CFG_DIR/runefix/config"
+run "XDG_CONFIG_HOME=$CFG_DIR cargo run --quiet -- width -p demo '→'"
+run "XDG_CONFIG_HOME=$CFG_DIR cargo run --quiet -- widths -p demo '→👩‍👩‍👧'"
+rm -rf "$CFG_DIR"
+echo -e "✅ Config-defined policy selected"
+
+section "Width Overrides"
+run 'cargo run --quiet -- widths --override U+E0B0=2 "ab"'
+run 'cargo run --quiet -- width --override nerd-fonts " "'
+run 'cargo run --quiet -- graphemes --override U+E0B0=2 "ab"'   # graphemes honors overrides too
+OVERRIDE_FILE=$(mktemp)
+printf 'U+E0A0..U+E0D4 = 2  # powerline\n' > "$OVERRIDE_FILE"
+run "cargo run --quiet -- width --override-file $OVERRIDE_FILE ''"
+rm -f "$OVERRIDE_FILE"
+echo -e "✅ Width overrides applied"
+
+section "Ambiguous Width"
+run 'cargo run --quiet -- widths --ambiguous wide "±→αЖ"'
+run 'env -u LC_ALL -u LC_CTYPE -u RUNEFIX_AMBIGUOUS LANG=ja_JP.UTF-8 cargo run --quiet -- split -w 4 --ambiguous auto "αβγδ"'
+run 'RUNEFIX_AMBIGUOUS=narrow cargo run --quiet -- slice -w "[0:2]" --ambiguous auto "αβγ"'
+echo -e "✅ Ambiguous-width characters sized by option and locale"
+
+section "Unicode Versions"
+run 'cargo run --quiet -- widths --unicode-version 13.0 "🫨🥹😀"'
+run 'cargo run --quiet -- truncate -w 4 --unicode-version 9.0 "🫠🥰🤣"'
+run 'cargo run --quiet -- unicode-diff 13.0 15.1 | tail -n 4'
+run 'cargo run --quiet -- unicode-diff 9.0 11.0 -p markdown -v | sed -n 1,8p'
+run 'cargo run --quiet -- unicode-diff 15.1 16.0'                              # Only the 7 new emoji
+run 'cargo run --quiet -- --unicode-version 13.0 export-table --format c | head -n 1'
+echo -e "✅ Newer code points measured as tofu"
+
+section "Expand / Unexpand Commands"
+run 'printf "世界\tx\nab\ty\n" | cargo run --quiet -- expand -t 4'
+run 'printf "        x  y\n" | cargo run --quiet -- unexpand -t 4 --all | cat -A'
+run 'printf "世\tx" | cargo run --quiet -- width -t 4,8'
+run 'printf "世\tabc" | cargo run --quiet -- truncate --width 5 -t 4 | cat -A'
+run 'rc=0; printf "a\tb" | cargo run --quiet -- width -t 18446744073709551615 || rc=$?; test $rc -eq 2'   # ⚠️ RF0005 stops are capped
+run 'rc=0; printf "a\tb" | cargo run --quiet -- expand -t 9223372036854775807 || rc=$?; test $rc -eq 2'   # ⚠️ RF0005
+echo -e "✅ Tab stops measured by display column"
+
+section "Flatten Command / --render-cr"
+run 'printf "Progress 10%%\rProgress 100%%\n" | cargo run --quiet -- flatten'
+run 'printf "ab你好\rxyz\n" | cargo run --quiet -- flatten'
+run 'printf "下载 10%%\r下载 100%% 完成\n" | cargo run --quiet -- width --render-cr'
+run 'printf "a\033[99999999999Cb" | cargo run --quiet -- flatten | wc -c'     # Huge cursor move is clamped
+run 'printf "a\tb\rX\n" | cargo run --quiet -- flatten -t 4 | cat -A'   # Tabs follow -t
+echo -e "✅ Carriage returns replayed before measuring"
+
+section "Box Command"
+run 'cargo run --quiet -- box --title "部署 🚀" --align center "Hello 世界"'
+run 'printf "ok 👩‍👩‍👧\nplain\n" | cargo run --quiet -- box --style double --padding 2'
+run 'rc=0; cargo run --quiet -- box --padding 18446744073709551615 x || rc=$?; test $rc -eq 2'   # Padding is bounded
+echo -e "✅ Box borders aligned"
+
+section "Ruler Command"
+run 'cargo run --quiet -- ruler "Hello 👋 世界" -v'
+run 'printf "a\xe2\x80\x8db 世\nabc\n" | cargo run --quiet -- ruler --mark 8 --position both'
+run 'rc=0; cargo run --quiet -- ruler --mark 100000000000 ab || rc=$?; test $rc -eq 2'   # Guide columns are bounded
+echo -e "✅ Column ruler rendered"
+
+section "Show Command"
+run 'cargo run --quiet -- show "你好a👩‍👩‍👧"'
+run 'printf "1️⃣\tx\n" | cargo run --quiet -- show -v'
+echo -e "✅ Cell occupancy grid rendered"
+
+section "Screen Command"
+run 'printf "hello 世界\033[2;5H\033[1;31mRED\033[0m\033[3;1Habcdefghij世" | cargo run --quiet -- screen --cols 10 --rows 4 --frame'
+run 'printf "ab\033[31mc" | cargo run --quiet -- screen --cols 4 --rows 1 --format json'
+run 'test "$(printf "ab" | cargo run --quiet -- --format ndjson screen --cols 2 --rows 1 | wc -l)" -eq 1'   # ndjson prints one compact line
+run 'printf "a\033[18446744073709551615Cb" | cargo run --quiet -- screen --cols 5 --rows 2'   # Huge cursor move saturates
+run 'printf "a\tb\n" | cargo run --quiet -- screen --cols 10 --rows 1 -t 3 | cat -A'   # Tabs follow -t
+echo -e "✅ Virtual screen replayed"
+
+section "Probe Command"
+run 'cargo run --quiet -- probe --emulate markdown'
+run 'cargo run --quiet -- probe --emulate compact --save /tmp/runefix-probe.toml --name compact-term'
+run 'printf "lang = \"ja-JP\"\n" > /tmp/runefix-probe.toml && cargo run --quiet -- probe --emulate compact --save /tmp/runefix-probe.toml > /dev/null && grep -q "^lang" /tmp/runefix-probe.toml && cat /tmp/runefix-probe.toml'   # Other entries kept
+echo -e "✅ Terminal probe compared against policies"
+
+section "Testpattern Command"
+run 'cargo run --quiet -- testpattern'
+run 'cargo run --quiet -- testpattern -c cjk,zwj,flag -p markdown -v'
+echo -e "✅ Test pattern printed"
+
+section "Policy Diff Command"
+run 'cargo run --quiet -- policy diff terminal markdown "a👍🏽中✅ é"'
+run 'cargo run --quiet -- policy diff terminal markdown compact "Hello 👋 世界" -v'
+run 'cargo run --quiet -- policy diff xterm kitty < /dev/null | tail -n 3'
+echo -e "✅ Policy diff listed"
+
+section "Compare Command"
+run 'LC_ALL=C.UTF-8 cargo run --quiet -- compare "a👍🏽中✅ é👨‍👩‍👧 1️⃣" -p terminal,markdown'
+run 'LC_ALL=C.UTF-8 cargo run --quiet -- compare "中✅" --against libc --format json -v'
+run 'LC_ALL=C.UTF-8 cargo run --quiet -- compare "中✅" --against libc --format ndjson -v'
+run 'LC_ALL=C.UTF-8 cargo run --quiet -- compare < /dev/null | tail -n 2'
+echo -e "✅ libc comparison printed"
+
+section "Export Table Command"
+run 'cargo run --quiet -- export-table --format c | head -n 12'
+run 'cargo run --quiet -- export-table -p markdown -f python | grep -A3 "^SEQUENCES"'
+run 'cargo run --quiet -- export-table -f json | head -n 8'
+run 'cargo run --quiet -- export-table -f go -o /tmp/runefix_widths.go && head -n 8 /tmp/runefix_widths.go'
+echo -e "✅ Width tables exported"
+
+section "Structured Output (--format)"
+run 'cargo run --quiet -- widths --format json "a\"世"'
+run 'cargo run --quiet -- atoms --format ndjson "👍🏽é"'
+run 'cargo run --quiet -- split -w 4 --format ndjson "Hello 世界"'
+run 'cargo run --quiet -- truncate -w 5 --format json "Hello 世界"'
+run 'printf "Hello 世界\nab\n" | cargo run --quiet -- slice "[0:3]" --strict --format ndjson'
+run 'cargo run --quiet -- version --format ndjson'
+echo -e "✅ JSON / NDJSON output emitted"
+
+section "Tabular Output (--format csv/tsv)"
+run 'cargo run --quiet -- widths --format csv "中→α😀,\"1️⃣🇯🇵👍🏽"'
+run 'cargo run --quiet -- atoms --format csv "👩‍🦰"'
+run 'printf "a\tb\\\\c\001" | cargo run --quiet -- graphemes --format tsv | cat -A'
+echo -e "✅ CSV / TSV rows quoted and escaped"
+
+section "Report Output (--format html/svg)"
+run 'cargo run --quiet -- atoms --format html "👩🏼‍🏫<1️⃣>" | grep -E "<html|class=\"cell|<li>"'
+run 'cargo run --quiet -- graphemes --format svg "Hello 👋 世界" | sed -n 1,2p'
+run 'cargo run --quiet -- graphemes --format svg "a&b" > /tmp/runefix_graphemes.svg && python3 -c "import xml.dom.minidom as m; m.parse(\"/tmp/runefix_graphemes.svg\"); print(\"well-formed\")"'
+echo -e "✅ HTML / SVG reports rendered"
+
+section "Color Control (--color / NO_COLOR)"
+run 'cargo run --quiet -- show "中a" --color always | cat -v'
+run 'CLICOLOR_FORCE=1 cargo run --quiet -- width -v "中a" | cat -v'
+run 'NO_COLOR=1 CLICOLOR_FORCE=1 cargo run --quiet -- show "中a" | cat -v'
+run 'cargo run --quiet -- testpattern -c cjk --color never | cat -v'
+echo -e "✅ Color forced and suppressed"
+
+section "Themes"
+run 'cargo run --quiet -- graphemes --color always "a中👩‍👩‍👧👍🏽" | cat -v | grep "\^\["'
+run 'cargo run --quiet -- slice -v "[2:50]" --color always "hello" | cat -v | tail -n 1'
+CFG_DIR=$(mktemp -d)
+mkdir -p "$CFG_DIR/runefix"
+printf '[theme]\npreset = none\nzwj = white on red\n' > "$CFG_DIR/runefix/config"
+run "XDG_CONFIG_HOME=$CFG_DIR cargo run --quiet -- widths --color always '中👩‍👩‍👧' | cat -v"
+rm -rf "$CFG_DIR"
+echo -e "✅ Theme colors applied"
+
+section "Tables (Localized Headers / Terminal Width)"
+CFG_DIR=$(mktemp -d)
+mkdir -p "$CFG_DIR/runefix"
+printf 'lang = zh-CN\n' > "$CFG_DIR/runefix/config"
+run "XDG_CONFIG_HOME=$CFG_DIR cargo run --quiet -- atoms '中a👍🏽'"
+run "XDG_CONFIG_HOME=$CFG_DIR cargo run --quiet -- policy diff terminal markdown '✅中'"
+rm -rf "$CFG_DIR"
+run 'COLUMNS=20 cargo run --quiet -- graphemes "你好, world"'
+echo -e "✅ Tables aligned"
+
+section "Broken Pipe (| head)"
+run 'yes "hello 世界 0123456789" | head -n 50000 | cargo run --quiet -- slice [0:10] | head -n 2'
+run 'cargo run --quiet -- testpattern | head -n 1; test ${PIPESTATUS[0]} -eq 0'
+echo -e "✅ Closed pipes end the run quietly"
+
+section "Error Codes and Exit Statuses"
+run 'rc=0; cargo run --quiet -- slice --strict "[0:9]" "ab" || rc=$?; test $rc -eq 4'  # ⚠️ RF0103
+run 'rc=0; cargo run --quiet -- width --policy bogus "ab" || rc=$?; test $rc -eq 2'  # ⚠️ RF0003
+run 'rc=0; echo "ab" | cargo run --quiet -- truncate || rc=$?; test $rc -eq 2'  # ⚠️ RF0002
+run 'rc=0; printf "\\xff" | cargo run --quiet -- atoms || rc=$?; test $rc -eq 3'  # ⚠️ RF0202
+run 'rc=0; cargo run --quiet -- box --format json "hi" || rc=$?; test $rc -eq 2'  # ⚠️ RF0001
+echo -e "✅ Errors carry stable codes and exit statuses"
+
 section "Version Command"
 run 'cargo run --quiet -- version'
 run 'cargo run --quiet -- version --json'
@@ -84,6 +265,10 @@ section "Slice: Options (-g / -c / -w)"
 run 'cargo run --quiet -- slice -g "[0:3]" "你好👨‍👩‍👧‍👦Hello世界"'
 run 'cargo run --quiet -- slice -c "[0:5]" "你好👨‍👩‍👧‍👦Hello世界"'
 run 'cargo run --quiet -- slice -w "[0:6]" "你好👨‍👩‍👧‍👦Hello世界"'
+run 'cargo run --quiet -- slice -w "[4:]" "你好👨‍👩‍👧‍👦Hello世界"'          # Open end runs to the last column
+run 'cargo run --quiet -- slice -w -v "[0:9]" "abc"'                     # Clamped, marked out of range
+run 'cargo run --quiet -- slice "[3:1]" "abcd"'                          # Reversed range is empty
+run 'printf "a\n\nb" | cargo run --quiet -- slice --format ndjson -w "[0:1]"'
 echo -e "✅ Mode flags (-g / -c / -w) working"
 
 section "Slice: Options (-s --strict)"
@@ -3,7 +3,17 @@
 //! Defines top-level commands, options, and subcommand-specific arguments
 //! using `clap` derive macros.
 
+use crate::commands::cmds::compare::CompareTarget;
+use crate::commands::cmds::ruler::RulerPosition;
+use crate::commands::output::OutputFormat;
+use crate::commands::policy::AmbiguousWidth;
+use crate::commands::samples::SampleCategory;
+use crate::commands::unicode_age::{UnicodeVersion, parse_unicode_version};
+use crate::style::frame::{Align, BorderStyle};
+use crate::style::term::{ColorChoice, WidthSpec};
+use clap::builder::RangedU64ValueParser;
 use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
 
 // Top-level CLI entrypoint.
 //
@@ -21,6 +31,36 @@ pub struct Cli {
     /// Subcommand to execute
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Width override: RANGE=WIDTH (e.g. U+E0B0=1, U+E0A0..U+E0D4=2) or a preset (nerd-fonts, nerd-fonts-mono)
+    #[arg(long = "override", global = true, value_name = "SPEC")]
+    pub overrides: Vec<String>,
+
+    /// File with one RANGE = WIDTH override per line
+    #[arg(long, global = true, value_name = "FILE")]
+    pub override_file: Vec<PathBuf>,
+
+    /// Width of East Asian Ambiguous characters (auto: from RUNEFIX_AMBIGUOUS or the locale)
+    #[arg(long, global = true, value_enum)]
+    pub ambiguous: Option<AmbiguousWidth>,
+
+    /// Measure as a terminal with this Unicode version's tables (newer code points count as 1 column)
+    #[arg(long, global = true, value_name = "VERSION", value_parser = parse_unicode_version)]
+    pub unicode_version: Option<UnicodeVersion>,
+
+    /// Output format: text, json (one document), ndjson (one object per line), csv/tsv (atoms, graphemes, widths), html/svg reports (atoms, graphemes) or the export-table language (c, rust, go, python, json)
+    #[arg(short, long, global = true, value_enum, default_value = "text")]
+    pub format: OutputFormat,
+
+    /// When to use colors and bold text: auto (TTY, honoring NO_COLOR / CLICOLOR_FORCE), always or never
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "WHEN",
+        default_value = "auto"
+    )]
+    pub color: ColorChoice,
 }
 
 // Supported `runefix` subcommands.
@@ -47,6 +87,45 @@ pub enum Commands {
     /// Slice input using [start:end] style expression
     Slice(SliceArgs),
 
+    /// Replay \r, \b and erase-in-line to show final visible lines
+    Flatten(FlattenArgs),
+
+    /// Convert tabs to spaces using display-column tab stops
+    Expand(ExpandArgs),
+
+    /// Convert blanks to tabs using display-column tab stops
+    Unexpand(UnexpandArgs),
+
+    /// Draw a width-aware box around input lines
+    Box(BoxArgs),
+
+    /// Overlay a column ruler to inspect cell layout
+    Ruler(RulerArgs),
+
+    /// Render text as a grid of terminal cells
+    Show(ShowArgs),
+
+    /// Replay terminal output on a virtual screen
+    Screen(ScreenArgs),
+
+    /// Measure how the terminal really renders sample graphemes
+    Probe(ProbeArgs),
+
+    /// Print alignment grids to eyeball terminal width disagreements
+    Testpattern(TestpatternArgs),
+
+    /// Inspect and compare width policies
+    Policy(PolicyArgs),
+
+    /// Compare runefix widths against another implementation (e.g. libc wcwidth)
+    Compare(CompareArgs),
+
+    /// Generate a width lookup table for other languages
+    ExportTable(ExportTableArgs),
+
+    /// List code points whose width changed between two Unicode versions
+    UnicodeDiff(UnicodeDiffArgs),
+
     /// Show version information
     Version(VersionArgs),
 
@@ -67,10 +146,18 @@ pub struct PolicyInput {
     /// Input text to measure (can be piped via stdin)
     pub text: Option<String>,
 
-    /// Layout policy: terminal, markdown, or compact
+    /// Layout policy: terminal, markdown, compact, an emulator profile (e.g. kitty), or auto
     #[arg(short, long, default_value = "terminal")]
     pub policy: String,
 
+    /// Tab stops in columns: N or a list like 4,8,12
+    #[arg(short = 't', long, value_name = "STOPS")]
+    pub tabstop: Option<String>,
+
+    /// Apply \r, \b and erase-in-line sequences before measuring
+    #[arg(long)]
+    pub render_cr: bool,
+
     /// Enable verbose output (e.g. titles)
     #[arg(short = 'v', long)]
     pub verbose: bool,
@@ -82,19 +169,287 @@ pub struct WidthInput {
     /// Input text to measure (can be piped via stdin)
     pub text: Option<String>,
 
-    /// Max display width in columns
+    /// Max display width: columns, auto (terminal width, the default on a TTY) or -N (terminal width minus N)
+    #[arg(short, long, value_name = "WIDTH", allow_hyphen_values = true)]
+    pub width: Option<WidthSpec>,
+
+    /// Layout policy: terminal, markdown, compact, an emulator profile (e.g. kitty), or auto
+    #[arg(short, long, default_value = "terminal")]
+    pub policy: String,
+
+    /// Tab stops in columns: N or a list like 4,8,12
+    #[arg(short = 't', long, value_name = "STOPS")]
+    pub tabstop: Option<String>,
+
+    /// Apply \r, \b and erase-in-line sequences before measuring
+    #[arg(long)]
+    pub render_cr: bool,
+
+    /// Enable verbose output (e.g. titles)
+    #[arg(short = 'v', long)]
+    pub verbose: bool,
+}
+
+// Arguments for `runefix flatten`
+#[derive(Args)]
+pub struct FlattenArgs {
+    /// Captured output to flatten (can be piped via stdin)
+    pub text: Option<String>,
+
+    /// Tab stops in columns: N or a list like 4,8,12
+    #[arg(short = 't', long, value_name = "STOPS", default_value = "8")]
+    pub tabstop: String,
+
+    /// Layout policy: terminal, markdown, compact, an emulator profile (e.g. kitty), or auto
+    #[arg(short, long, default_value = "terminal")]
+    pub policy: String,
+}
+
+// Arguments for `runefix expand`
+#[derive(Args)]
+pub struct ExpandArgs {
+    /// Input text to expand (can be piped via stdin)
+    pub text: Option<String>,
+
+    /// Tab stops in columns: N or a list like 4,8,12
+    #[arg(short = 't', long, value_name = "STOPS", default_value = "8")]
+    pub tabstop: String,
+
+    /// Only expand tabs before the first non-blank character
+    #[arg(short, long)]
+    pub initial: bool,
+
+    /// Layout policy: terminal, markdown, compact, an emulator profile (e.g. kitty), or auto
+    #[arg(short, long, default_value = "terminal")]
+    pub policy: String,
+}
+
+// Arguments for `runefix unexpand`
+#[derive(Args)]
+pub struct UnexpandArgs {
+    /// Input text to unexpand (can be piped via stdin)
+    pub text: Option<String>,
+
+    /// Tab stops in columns: N or a list like 4,8,12
+    #[arg(short = 't', long, value_name = "STOPS", default_value = "8")]
+    pub tabstop: String,
+
+    /// Convert all blank runs, not only leading ones
+    #[arg(short, long)]
+    pub all: bool,
+
+    /// Layout policy: terminal, markdown, compact, an emulator profile (e.g. kitty), or auto
+    #[arg(short, long, default_value = "terminal")]
+    pub policy: String,
+}
+
+// Arguments for `runefix box`
+#[derive(Args)]
+pub struct BoxArgs {
+    /// Input text to frame (can be piped via stdin)
+    pub text: Option<String>,
+
+    /// Border style
+    #[arg(short, long, value_enum, default_value_t = BorderStyle::Single)]
+    pub style: BorderStyle,
+
+    /// Title embedded in the top border
     #[arg(short, long)]
-    pub width: usize,
+    pub title: Option<String>,
+
+    /// Spaces between border and content (0-64)
+    #[arg(long, default_value_t = 1, value_parser = RangedU64ValueParser::<usize>::new().range(..=64))]
+    pub padding: usize,
+
+    /// Horizontal alignment of each line
+    #[arg(short, long, value_enum, default_value_t = Align::Left)]
+    pub align: Align,
+
+    /// Layout policy: terminal, markdown, compact, an emulator profile (e.g. kitty), or auto
+    #[arg(short, long, default_value = "terminal")]
+    pub policy: String,
+}
+
+// Arguments for `runefix ruler`
+#[derive(Args)]
+pub struct RulerArgs {
+    /// Input text to inspect (can be piped via stdin)
+    pub text: Option<String>,
+
+    /// Guide column to highlight, 0-4095 (repeatable)
+    #[arg(short, long, value_name = "COLUMN", value_parser = RangedU64ValueParser::<usize>::new().range(..4096))]
+    pub mark: Vec<usize>,
+
+    /// Where to draw the ruler relative to each line
+    #[arg(long, value_enum, default_value_t = RulerPosition::Above)]
+    pub position: RulerPosition,
 
-    /// Layout policy: terminal, markdown, or compact
+    /// Layout policy: terminal, markdown, compact, an emulator profile (e.g. kitty), or auto
     #[arg(short, long, default_value = "terminal")]
     pub policy: String,
 
+    /// Enable verbose output (e.g. titles, legends)
+    #[arg(short = 'v', long)]
+    pub verbose: bool,
+}
+
+// Arguments for `runefix show`
+#[derive(Args)]
+pub struct ShowArgs {
+    /// Input text to visualize (can be piped via stdin)
+    pub text: Option<String>,
+
+    /// Enable verbose output (e.g. titles, per-cell details, legends)
+    #[arg(short = 'v', long)]
+    pub verbose: bool,
+}
+
+// Arguments for `runefix screen`
+#[derive(Args)]
+pub struct ScreenArgs {
+    /// Terminal output to replay (can be piped via stdin)
+    pub text: Option<String>,
+
+    /// Screen width in columns
+    #[arg(long, default_value_t = 80, value_parser = clap::value_parser!(u16).range(1..))]
+    pub cols: u16,
+
+    /// Screen height in rows
+    #[arg(long, default_value_t = 24, value_parser = clap::value_parser!(u16).range(1..))]
+    pub rows: u16,
+
+    /// Tab stops in columns: N or a list like 4,8,12
+    #[arg(short = 't', long, value_name = "STOPS", default_value = "8")]
+    pub tabstop: String,
+
+    /// Layout policy: terminal, markdown, compact, an emulator profile (e.g. kitty), or auto
+    #[arg(short, long, default_value = "terminal")]
+    pub policy: String,
+
+    /// Draw a border around the screen
+    #[arg(long)]
+    pub frame: bool,
+}
+
+// Arguments for `runefix probe`
+#[derive(Args)]
+pub struct ProbeArgs {
+    /// Probe a virtual terminal using this policy instead of the real one
+    #[arg(long, value_name = "POLICY")]
+    pub emulate: Option<String>,
+
+    /// Write a policy derived from the measurements to this file, keeping its other entries
+    #[arg(long, value_name = "FILE")]
+    pub save: Option<PathBuf>,
+
+    /// Name of the saved policy section
+    #[arg(long, default_value = "probed", requires = "save")]
+    pub name: String,
+
+    /// Enable verbose output (e.g. titles, all samples)
+    #[arg(short = 'v', long)]
+    pub verbose: bool,
+}
+
+// Arguments for `runefix testpattern`
+#[derive(Args)]
+pub struct TestpatternArgs {
+    /// Only print these sample categories (comma-separated or repeated)
+    #[arg(short, long = "category", value_enum, value_delimiter = ',')]
+    pub categories: Vec<SampleCategory>,
+
+    /// Layout policy: terminal, markdown, compact, an emulator profile (e.g. kitty), or auto
+    #[arg(short, long, default_value = "terminal")]
+    pub policy: String,
+
+    /// Enable verbose output (e.g. titles, expected widths, legends)
+    #[arg(short = 'v', long)]
+    pub verbose: bool,
+}
+
+// Arguments for `runefix policy`
+#[derive(Args)]
+pub struct PolicyArgs {
+    #[command(subcommand)]
+    pub command: PolicyCommand,
+}
+
+/// Subcommands of `runefix policy`
+#[derive(Subcommand)]
+pub enum PolicyCommand {
+    /// List graphemes whose width differs between policies
+    Diff(PolicyDiffArgs),
+}
+
+// Arguments for `runefix policy diff`
+#[derive(Args)]
+pub struct PolicyDiffArgs {
+    /// Two or more policies to compare, optionally followed by the text to segment
+    /// (without text, all assigned code points and common emoji sequences are scanned)
+    #[arg(value_name = "POLICY... [TEXT]", required = true)]
+    pub args: Vec<String>,
+
     /// Enable verbose output (e.g. titles)
     #[arg(short = 'v', long)]
     pub verbose: bool,
 }
 
+// Arguments for `runefix compare`
+#[derive(Args)]
+pub struct CompareArgs {
+    /// Input text (reads stdin if omitted; scans all code points when there is none)
+    pub text: Option<String>,
+
+    /// Width implementation to compare against
+    #[arg(long, value_enum, default_value = "libc")]
+    pub against: CompareTarget,
+
+    /// Policies to compare (comma-separated or repeated)
+    #[arg(
+        short,
+        long = "policy",
+        value_delimiter = ',',
+        default_value = "terminal"
+    )]
+    pub policies: Vec<String>,
+
+    /// Enable verbose output (e.g. titles, locale, matching graphemes)
+    #[arg(short = 'v', long)]
+    pub verbose: bool,
+}
+
+// Arguments for `runefix export-table`
+#[derive(Args)]
+pub struct ExportTableArgs {
+    /// Layout policy: terminal, markdown, compact, an emulator profile (e.g. kitty), or auto
+    #[arg(short, long, default_value = "terminal")]
+    pub policy: String,
+
+    /// Write the table to this file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+// Arguments for `runefix unicode-diff`
+#[derive(Args)]
+pub struct UnicodeDiffArgs {
+    /// Older Unicode version (e.g. 13.0)
+    #[arg(value_parser = parse_unicode_version)]
+    pub from: UnicodeVersion,
+
+    /// Newer Unicode version (e.g. 15.1)
+    #[arg(value_parser = parse_unicode_version)]
+    pub to: UnicodeVersion,
+
+    /// Layout policy: terminal, markdown, compact, an emulator profile (e.g. kitty), or auto
+    #[arg(short, long, default_value = "terminal")]
+    pub policy: String,
+
+    /// Enable verbose output (e.g. titles, one row per code point)
+    #[arg(short = 'v', long)]
+    pub verbose: bool,
+}
+
 // Arguments for `runefix version`
 #[derive(Args)]
 pub struct VersionArgs {
@@ -133,4 +488,8 @@ pub struct SliceArgs {
     /// Enable strict range checks (errors on overflow)
     #[clap(short = 's', long)]
     pub strict: bool,
+
+    /// Apply \r, \b and erase-in-line sequences before slicing
+    #[arg(long)]
+    pub render_cr: bool,
 }
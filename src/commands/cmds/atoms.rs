@@ -1,32 +1,34 @@
 //! Execute the `atoms` command: decompose text into layout-affecting atomic segments,
 //! and inspect Unicode codepoints and display widths.
 
+use super::report::{ReportRow, print_visual_report};
+use crate::commands::output::{
+    OutputFormat, code_points, print_report, print_segment_table, segment,
+};
+use crate::commands::policy::LayoutPolicy;
 use crate::config::Context;
 use crate::i18n::keys::{FooterKey, TitleKey};
-use crate::i18n::lang::Lang;
-use crate::style::{consts::WIDTH_LINE, print::*, term::*};
-use runefix_core::{RuneDisplayWidth, atoms};
-
-/// Convert a string into a Unicode codepoint sequence (e.g., U+1F600).
-fn str_to_unicode_sequence(s: &str) -> String {
-    s.chars()
-        .map(|c| format!("U+{:04X}", c as u32))
-        .collect::<Vec<_>>()
-        .join(" ")
-}
+use crate::style::frame::Align;
+use crate::style::table::Table;
+use crate::style::theme::Role;
+use crate::style::{print::*, term::*};
+use anyhow::Result;
+use runefix_core::atoms;
+use serde_json::json;
+use std::io::Write;
 
 /// Return true if character is a Zero Width Joiner (ZWJ)
-fn is_zwj(s: &str) -> bool {
+pub(super) fn is_zwj(s: &str) -> bool {
     s == "\u{200D}"
 }
 
 /// Return true if character is an emoji variant selector (VS16)
-fn is_emoji_vs(s: &str) -> bool {
+pub(super) fn is_emoji_vs(s: &str) -> bool {
     s == "\u{FE0F}"
 }
 
 /// Return true if character is a combining enclosing keycap
-fn is_combining_mark(s: &str) -> bool {
+pub(super) fn is_combining_mark(s: &str) -> bool {
     s == "\u{20E3}"
 }
 
@@ -53,34 +55,88 @@ fn display_char(s: &str) -> &str {
 }
 
 /// Return display width for the segment (taking emoji modifiers into account)
-fn measured_width(s: &str) -> usize {
+fn measured_width(s: &str, policy: &LayoutPolicy) -> usize {
     if is_zwj(s) || is_emoji_vs(s) || is_combining_mark(s) {
         0
     } else if is_skin_tone_modifier(s) || is_hair_component(s) {
         2
     } else {
-        s.width()
+        policy.width(s)
     }
 }
 
-/// Return Unicode codepoint string and semantic label
-fn display_unicode_hint(s: &str) -> String {
-    let base = str_to_unicode_sequence(s);
+/// Return the semantic kind of a modifier atom, if any (for structured output)
+fn atom_kind(s: &str) -> Option<&'static str> {
+    if is_zwj(s) {
+        Some("zwj")
+    } else if is_emoji_vs(s) {
+        Some("emoji_variant")
+    } else if is_combining_mark(s) {
+        Some("combining_mark")
+    } else if is_skin_tone_modifier(s) {
+        Some("skin_tone")
+    } else if is_hair_component(s) {
+        Some("hair_colors")
+    } else {
+        None
+    }
+}
+
+/// Return the semantic label of a modifier atom, if any (e.g. `Skin Tone`)
+pub(super) fn unicode_hint(s: &str) -> Option<&'static str> {
     if is_zwj(s) {
-        format!("{:7} (ZWJ)", base)
+        Some("ZWJ")
     } else if is_emoji_vs(s) {
-        format!("{:7} (Emoji Variant)", base)
+        Some("Emoji Variant")
     } else if is_combining_mark(s) {
-        format!("{:7} (Combining Mark)", base)
+        Some("Combining Mark")
     } else if is_skin_tone_modifier(s) {
-        format!("{:7} (Skin Tone)", base)
+        Some("Skin Tone")
     } else if is_hair_component(s) {
-        format!("{:7} (Hair Colors)", base)
+        Some("Hair Colors")
+    } else {
+        None
+    }
+}
+
+/// Return the highlight role of an atom or grapheme cluster, if any
+pub(super) fn segment_role(s: &str, width: usize) -> Option<Role> {
+    if s.chars().any(char::is_control) {
+        Some(Role::Control)
+    } else if s.contains('\u{200D}') {
+        Some(Role::Zwj)
+    } else if atoms(s)
+        .iter()
+        .any(|a| is_skin_tone_modifier(a) || is_hair_component(a))
+    {
+        Some(Role::Modifier)
+    } else if width == 0 {
+        Some(Role::ZeroWidth)
+    } else if width >= 2 {
+        Some(Role::Wide)
     } else {
-        base
+        None
     }
 }
 
+/// Return Unicode codepoint string and semantic label
+pub(super) fn display_unicode_hint(s: &str) -> String {
+    let base = code_points(s).join(" ");
+    match unicode_hint(s) {
+        Some(hint) => format!("{:7} ({})", base, hint),
+        None => base,
+    }
+}
+
+/// Glossary of the modifier atoms labelled by [`unicode_hint`]
+pub(super) const GLOSSARY: [&str; 5] = [
+    "ZWJ = Zero Width Joiner (U+200D)",
+    "Emoji Variant = U+FE0F (Forces Emoji Presentation)",
+    "Combining Mark = U+20E3 (Combining Enclosing Keycap)",
+    "Skin Tone = U+1F3FB–U+1F3FF (Fitzpatrick Modifiers)",
+    "Hair Colors = U+1F9B0–U+1F9B3 (Hair/Beard modifiers)",
+];
+
 /// Decompose a string into atomic segments and inspect each for Unicode info.
 ///
 /// This command is especially useful for inspecting emoji sequences, ZWJ chains,
@@ -89,71 +145,102 @@ fn display_unicode_hint(s: &str) -> String {
 ///
 /// # Arguments
 /// * `ctx` - Global context (i18n, language settings, etc.)
+/// * `out` - Output writer
 /// * `input` - Input text to be segmented
-pub fn run_atoms(ctx: &Context, input: &str) {
-    // Show localized title header
-    print_title(&ctx.t(TitleKey::UnicodeInfo));
-
+/// * `policy` - Width policy for non-modifier atoms
+/// * `format` - Output format (`text`, `json`, `ndjson`, `csv`, `tsv`, `html`, `svg`)
+pub fn run_atoms(
+    ctx: &Context,
+    out: &mut dyn Write,
+    input: &str,
+    policy: &LayoutPolicy,
+    format: OutputFormat,
+) -> Result<()> {
     // Segment input into width-sensitive atoms
     let atoms = atoms(input);
 
-    // Print table header (bold if in terminal)
-    if is_stdout_terminal() {
-        println!(
-            " \x1b[1m{:<4}   {:<4}    {:<26}      {:>5}\x1b[0m",
-            "No.", "Rune", "Unicode / Hint", "Width"
-        );
-    } else {
-        println!(
-            " {:<4}   {:<4}    {:<26}      {:>5}",
-            "No.", "Rune", "Unicode / Hint", "Width"
-        );
+    // Structured output: one object per atom
+    if format.is_structured() {
+        let mut cursor = 0;
+        let items = atoms
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let mut atom = segment(input, &mut cursor, i, s, measured_width(s, policy));
+                atom["kind"] = json!(atom_kind(s));
+                atom
+            })
+            .collect();
+        let total: usize = atoms.iter().map(|s| measured_width(s, policy)).sum();
+        let fields = json!({
+            "command": "atoms",
+            "text": input,
+            "policy": policy.name,
+            "width": total,
+        });
+        print_report(out, format, fields, Some(("atoms", items)))?;
+        return Ok(());
     }
 
-    // Draw separator line
-    println!("{}", "─".repeat(WIDTH_LINE));
+    // Tabular output: one row per atom
+    if format.is_tabular() {
+        print_segment_table(
+            out,
+            format,
+            atoms.iter().map(|s| (*s, measured_width(s, policy))),
+        )?;
+        return Ok(());
+    }
 
-    // Print each atom's index, visual rune, Unicode info, and width
+    // Visual report: cell grid, hints and legend
+    if format.is_visual() {
+        let rows: Vec<ReportRow> = atoms
+            .iter()
+            .map(|s| ReportRow {
+                text: s,
+                width: measured_width(s, policy),
+                hint: unicode_hint(s),
+            })
+            .collect();
+        let title = ctx.t(TitleKey::UnicodeInfo);
+        print_visual_report(ctx, out, format, &title, input, Some(&policy.name), &rows)?;
+        return Ok(());
+    }
+
+    // Show localized title header
+    print_title(out, &ctx.t(TitleKey::UnicodeInfo))?;
+
+    // One row per atom: index, visual rune, Unicode info and width
+    let mut table = Table::new(["No.", "Rune", "Unicode / Hint", "Width"]).align(3, Align::Right);
     for (i, s) in atoms.iter().enumerate() {
-        let ch = display_char(s);
-        let w = measured_width(s);
-        let u = display_unicode_hint(s);
-        let spacing = match w {
-            2 => "   ",
-            _ => "    ",
-        };
-        println!(" {:02}     {:<4}{}{: <26}      {:>5}", i, ch, spacing, u, w);
+        let w = measured_width(s, policy);
+        let cells = vec![
+            format!("{i:02}"),
+            display_char(s).to_string(),
+            display_unicode_hint(s),
+            w.to_string(),
+        ];
+        table.styled_row(cells, segment_role(s, w));
     }
 
-    // Draw footer line
-    println!("{}", "─".repeat(WIDTH_LINE));
-
-    // Show total width summary (localized label, right-aligned)
-    let label = ctx.t(FooterKey::AtomsDetail);
-    let total: usize = atoms.iter().map(|s| measured_width(s)).sum();
-    let padding = if ctx.lang == Lang::EnUS { 26 } else { 35 };
-    println!(
-        "{:>padding$}「{}」: {:2}",
-        "",
-        label,
-        total,
-        padding = padding
+    // Total width summary (localized label)
+    let total: usize = atoms.iter().map(|s| measured_width(s, policy)).sum();
+    table.divider();
+    table.span_row(
+        format!("「{}」", ctx.t(FooterKey::AtomsDetail)),
+        3,
+        vec![total.to_string()],
     );
+    table.print(out, &ctx.theme)?;
 
-    // Print legend title (bold if terminal)
-    println!();
-    let legend = ctx.t(FooterKey::LegendGlossary);
-    let styled = if is_stdout_terminal() {
-        bold(&legend)
-    } else {
-        legend
-    };
-    println!("{styled}:");
+    // Print legend title (bold if color is enabled)
+    writeln!(out)?;
+    writeln!(out, "{}:", bold(&ctx.t(FooterKey::LegendGlossary)))?;
 
     // Print glossary of special Unicode atoms
-    println!("ZWJ = Zero Width Joiner (U+200D)");
-    println!("Emoji Variant = U+FE0F (Forces Emoji Presentation)");
-    println!("Combining Mark = U+20E3 (Combining Enclosing Keycap)");
-    println!("Skin Tone = U+1F3FB–U+1F3FF (Fitzpatrick Modifiers)");
-    println!("Hair Colors = U+1F9B0–U+1F9B3 (Hair/Beard modifiers)");
+    for line in GLOSSARY {
+        writeln!(out, "{line}")?;
+    }
+
+    Ok(())
 }
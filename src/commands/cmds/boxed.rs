@@ -0,0 +1,49 @@
+//! Execute the `box` command: wrap input lines in a width-aware frame.
+
+use crate::commands::policy::LayoutPolicy;
+use crate::style::frame::{Align, BorderStyle, FrameOptions, render_frame};
+use anyhow::Result;
+use std::io::Write;
+
+/// Draw a frame around each line of the input and print it.
+///
+/// Every line is measured with the given `LayoutPolicy`, so CJK and emoji
+/// content stays inside the right border.
+///
+/// # Arguments
+/// * `out` - Output writer.
+/// * `input` - Text to frame (one row per line).
+/// * `style` - Border glyph set (single, double, rounded, heavy, ascii).
+/// * `title` - Optional title embedded in the top border.
+/// * `padding` - Spaces between the border and the content on each side.
+/// * `align` - Horizontal alignment of each line.
+/// * `policy` - Display width strategy.
+pub fn run_box(
+    out: &mut dyn Write,
+    input: &str,
+    style: BorderStyle,
+    title: Option<&str>,
+    padding: usize,
+    align: Align,
+    policy: LayoutPolicy,
+) -> Result<()> {
+    // Keep at least one (empty) row so the frame is never degenerate
+    let mut lines: Vec<&str> = input.lines().collect();
+    if lines.is_empty() {
+        lines.push("");
+    }
+
+    let opts = FrameOptions {
+        style,
+        title,
+        padding,
+        align,
+        policy: &policy,
+    };
+
+    for row in render_frame(&lines, &opts) {
+        writeln!(out, "{row}")?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,235 @@
+//! Execute the `compare` command: measure graphemes with another width
+//! implementation (currently the C library's `wcwidth`) and report where it
+//! disagrees with runefix policies.
+
+use crate::commands::output::{OutputFormat, code_points, print_report};
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::samples::collect_candidates;
+use crate::config::Context;
+#[cfg(not(unix))]
+use crate::error::RunefixError;
+#[cfg(not(unix))]
+use crate::i18n::keys::ErrorKey;
+use crate::i18n::keys::{FooterKey, TitleKey};
+use crate::style::print::*;
+use crate::style::table::Table;
+use anyhow::Result;
+#[cfg(not(unix))]
+use anyhow::anyhow;
+use clap::ValueEnum;
+use serde_json::{Value, json};
+use std::io::Write;
+
+/// Width implementation to compare runefix against.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum CompareTarget {
+    /// The platform C library (`wcwidth` / `wcswidth` under the current locale)
+    #[default]
+    Libc,
+}
+
+/// Bindings to the C library's width functions.
+///
+/// The `libc` crate does not export `wcwidth`, so they are declared here.
+#[cfg(unix)]
+mod sys {
+    use std::ffi::CStr;
+
+    unsafe extern "C" {
+        fn wcwidth(c: libc::wchar_t) -> libc::c_int;
+        fn wcswidth(s: *const libc::wchar_t, n: libc::size_t) -> libc::c_int;
+    }
+
+    /// Applies the environment's `LC_CTYPE` and returns the locale name and
+    /// whether its codeset is UTF-8.
+    pub fn init_locale() -> (String, bool) {
+        // SAFETY: `c""` is a NUL-terminated C string literal. The strings returned by
+        // `setlocale` and `nl_langinfo` are NUL-terminated and stay valid until the
+        // next locale call; both are copied or compared before returning, and
+        // nothing else changes the locale in between.
+        unsafe {
+            let name = libc::setlocale(libc::LC_CTYPE, c"".as_ptr());
+            let name = if name.is_null() {
+                "C".to_string()
+            } else {
+                CStr::from_ptr(name).to_string_lossy().into_owned()
+            };
+            let codeset = CStr::from_ptr(libc::nl_langinfo(libc::CODESET)).to_string_lossy();
+            let utf8 =
+                codeset.eq_ignore_ascii_case("UTF-8") || codeset.eq_ignore_ascii_case("utf8");
+            (name, utf8)
+        }
+    }
+
+    /// `wcwidth` of a single code point (`None` for non-printable).
+    pub fn char_width(c: char) -> Option<usize> {
+        // SAFETY: `wcwidth` takes its argument by value. A `char` is at most
+        // 0x10FFFF, so it fits in `wchar_t` (an `i32` on Linux) without wrapping.
+        let w = unsafe { wcwidth(c as libc::wchar_t) };
+        usize::try_from(w).ok()
+    }
+
+    /// `wcswidth` of a whole grapheme (`None` if any code point is non-printable).
+    pub fn str_width(s: &str) -> Option<usize> {
+        let wide: Vec<libc::wchar_t> = s.chars().map(|c| c as libc::wchar_t).collect();
+        // SAFETY: `wide` is a live buffer of exactly `wide.len()` wide characters, each
+        // converted from a `char` (at most 0x10FFFF, so it fits in `wchar_t`), and
+        // `wcswidth` reads no more than `n` of them.
+        let w = unsafe { wcswidth(wide.as_ptr(), wide.len()) };
+        usize::try_from(w).ok()
+    }
+}
+
+/// Formats a libc width, using `-1` for non-printable like the C API does.
+fn libc_label(w: Option<usize>) -> String {
+    w.map_or_else(|| "-1".to_string(), |w| w.to_string())
+}
+
+/// Compare runefix policies against another width implementation.
+///
+/// # Arguments
+/// * `ctx` - Global application context.
+/// * `out` - Output writer.
+/// * `target` - Implementation to compare against.
+/// * `policies` - Runefix policies to compare.
+/// * `input` - Optional text to segment.
+/// * `format` - Output format (`text`, `json` or `ndjson`).
+/// * `verbose` - Enable verbose output (title, locale, all graphemes).
+pub fn run_compare(
+    ctx: &Context,
+    out: &mut dyn Write,
+    target: CompareTarget,
+    policies: &[LayoutPolicy],
+    input: Option<&str>,
+    format: OutputFormat,
+    verbose: bool,
+) -> Result<()> {
+    match target {
+        #[cfg(unix)]
+        CompareTarget::Libc => compare_libc(ctx, out, policies, input, format, verbose),
+        #[cfg(not(unix))]
+        CompareTarget::Libc => Err(anyhow!(RunefixError::new(
+            ctx,
+            ErrorKey::CompareLibcUnavailable
+        ))),
+    }
+}
+
+/// Compare runefix policies against the C library's `wcwidth` / `wcswidth`.
+///
+/// Graphemes come from `input` when given (each distinct grapheme once);
+/// otherwise all assigned code points plus common emoji sequences are scanned.
+/// Only mismatches are listed unless `verbose` is set.
+#[cfg(unix)]
+fn compare_libc(
+    ctx: &Context,
+    out: &mut dyn Write,
+    policies: &[LayoutPolicy],
+    input: Option<&str>,
+    format: OutputFormat,
+    verbose: bool,
+) -> Result<()> {
+    let (locale, utf8) = sys::init_locale();
+
+    if !utf8 {
+        eprintln!("⚠ Locale '{locale}' is not UTF-8; libc will reject most non-ASCII characters.");
+    }
+
+    let candidates = collect_candidates(input);
+
+    // Measure everything once, keeping only what the report needs
+    let rows: Vec<(&String, Option<usize>, Vec<usize>)> = candidates
+        .iter()
+        .map(|g| {
+            let widths = policies.iter().map(|p| p.width(g)).collect();
+            (g, sys::str_width(g), widths)
+        })
+        .collect();
+    let differs = |libc: Option<usize>, widths: &[usize]| widths.iter().any(|&w| Some(w) != libc);
+
+    let mut mismatches = vec![0; policies.len()];
+    for (_, libc, widths) in &rows {
+        for (i, &w) in widths.iter().enumerate() {
+            if Some(w) != *libc {
+                mismatches[i] += 1;
+            }
+        }
+    }
+
+    if format.is_structured() {
+        let results: Vec<Value> = rows
+            .iter()
+            .filter(|(_, libc, widths)| verbose || differs(*libc, widths))
+            .map(|(g, libc, widths)| {
+                let by_policy: serde_json::Map<String, Value> = policies
+                    .iter()
+                    .zip(widths)
+                    .map(|(p, w)| (p.name.clone(), json!(w)))
+                    .collect();
+                json!({
+                    "grapheme": g,
+                    "code_points": code_points(g),
+                    "libc": libc,
+                    "libc_code_points": g.chars().map(sys::char_width).collect::<Vec<_>>(),
+                    "widths": by_policy,
+                })
+            })
+            .collect();
+
+        let totals: serde_json::Map<String, Value> = policies
+            .iter()
+            .zip(&mismatches)
+            .map(|(p, m)| (p.name.clone(), json!(m)))
+            .collect();
+
+        let fields = json!({
+            "against": "libc",
+            "locale": locale,
+            "utf8": utf8,
+            "total": rows.len(),
+            "mismatches": totals,
+        });
+        print_report(out, format, fields, Some(("results", results)))?;
+        return Ok(());
+    }
+
+    // Optional header (only in verbose mode)
+    if verbose {
+        print_title(out, &ctx.t(TitleKey::CompareLibc))?;
+        writeln!(out, " LC_CTYPE = {locale}\n")?;
+    }
+
+    let mut headers = vec!["Grapheme".to_string(), "libc".to_string()];
+    headers.extend(policies.iter().map(|p| p.name.clone()));
+    headers.push("Code Points".to_string());
+    let mut table = Table::new(headers);
+
+    // Rows: every grapheme in verbose mode, otherwise only disagreements
+    for (g, libc, widths) in &rows {
+        if !verbose && !differs(*libc, widths) {
+            continue;
+        }
+
+        let mut cells = vec![g.to_string(), libc_label(*libc)];
+        for &w in widths {
+            cells.push(if Some(w) == *libc {
+                w.to_string()
+            } else {
+                format!("{w} ✗")
+            });
+        }
+        cells.push(code_points(g).join(" "));
+        table.row(cells);
+    }
+
+    // Totals
+    table.divider();
+    table.span_row(
+        ctx.t(FooterKey::ProbeMismatches),
+        2,
+        mismatches.iter().map(|m| m.to_string()).collect(),
+    );
+    table.print(out, &ctx.theme)?;
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+//! Execute the `expand` command: convert tabs to spaces using display-column tab stops.
+
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::tabs::{TabStops, expand_line};
+use anyhow::Result;
+use std::io::Write;
+
+/// Expand tabs in each input line to spaces, measuring columns by display width.
+///
+/// Unlike byte-based tools, wide characters before a tab shorten it correctly.
+///
+/// # Arguments
+/// * `out` - Output writer.
+/// * `input` - Text to expand (processed line by line).
+/// * `stops` - Tab stop positions.
+/// * `policy` - Display width strategy.
+/// * `initial` - Only expand tabs before the first non-blank character.
+pub fn run_expand(
+    out: &mut dyn Write,
+    input: &str,
+    stops: &TabStops,
+    policy: LayoutPolicy,
+    initial: bool,
+) -> Result<()> {
+    for line in input.lines() {
+        writeln!(out, "{}", expand_line(line, stops, &policy, initial))?;
+    }
+
+    Ok(())
+}
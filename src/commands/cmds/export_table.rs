@@ -0,0 +1,288 @@
+//! Execute the `export-table` command: generate width lookup tables for
+//! other languages from a layout policy.
+//!
+//! The table is a sorted list of `(first, last, width)` code point ranges for
+//! every width other than 1, plus the multi-code-point sequences whose width
+//! differs from that of their first code point. Consumers segment text into
+//! graphemes and look each one up in the sequence table first, falling back to
+//! the range width of its first code point.
+
+use crate::commands::output::OutputFormat;
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::samples::scan_candidates;
+use anyhow::Result;
+use runefix_core::UNICODE_VERSION;
+use serde_json::json;
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Runefix CLI version (from Cargo.toml)
+const CLI_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Width assumed for code points outside every range.
+const DEFAULT_WIDTH: usize = 1;
+
+/// Variation selectors tried after each code point (text / emoji presentation).
+const SELECTORS: [char; 2] = ['\u{FE0E}', '\u{FE0F}'];
+
+/// Output language of the generated table.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum TableFormat {
+    C,
+    #[default]
+    Rust,
+    Go,
+    Json,
+    Python,
+}
+
+impl TableFormat {
+    /// Maps the global `--format` onto a table language (`text` selects the
+    /// default, Rust); `None` for formats `export-table` can't produce.
+    pub fn from_output(format: OutputFormat) -> Option<Self> {
+        match format {
+            OutputFormat::Text => Some(TableFormat::default()),
+            OutputFormat::C => Some(TableFormat::C),
+            OutputFormat::Rust => Some(TableFormat::Rust),
+            OutputFormat::Go => Some(TableFormat::Go),
+            OutputFormat::Json => Some(TableFormat::Json),
+            OutputFormat::Python => Some(TableFormat::Python),
+            _ => None,
+        }
+    }
+}
+
+/// A width table derived from a policy.
+struct WidthTable {
+    ranges: Vec<(u32, u32, usize)>,
+    sequences: Vec<(String, usize)>,
+}
+
+/// Width of a single code point under `policy`.
+fn char_width(policy: &LayoutPolicy, c: char) -> usize {
+    let mut buf = [0; 4];
+    policy.width(c.encode_utf8(&mut buf))
+}
+
+/// Measures every scalar value and the common sequences under `policy`.
+fn build_table(policy: &LayoutPolicy) -> WidthTable {
+    // Merge consecutive code points of equal (non-default) width into ranges
+    let mut ranges: Vec<(u32, u32, usize)> = Vec::new();
+    for c in '\0'..=char::MAX {
+        let w = char_width(policy, c);
+        if w == DEFAULT_WIDTH {
+            continue;
+        }
+        let cp = c as u32;
+        match ranges.last_mut() {
+            Some((_, last, width)) if *last + 1 == cp && *width == w => *last = cp,
+            _ => ranges.push((cp, cp, w)),
+        }
+    }
+
+    // Variation sequences, for code points that take emoji presentation
+    let terminal = LayoutPolicy::terminal();
+    let mut candidates: Vec<String> = ('\u{A0}'..='\u{1FFFF}')
+        .filter(|c| terminal.width(&format!("{c}\u{FE0F}")) == 2)
+        .flat_map(|c| SELECTORS.map(|vs| format!("{c}{vs}")))
+        .collect();
+
+    // Sequences whose width is not that of their first code point
+    candidates.extend(
+        scan_candidates()
+            .into_iter()
+            .filter(|s| s.chars().count() > 1),
+    );
+
+    let mut seen = HashSet::new();
+    let mut sequences: Vec<(String, usize)> = Vec::new();
+    for s in candidates {
+        let Some(first) = s.chars().next() else {
+            continue;
+        };
+        let w = policy.width(&s);
+        if w != char_width(policy, first) && seen.insert(s.clone()) {
+            sequences.push((s, w));
+        }
+    }
+
+    WidthTable { ranges, sequences }
+}
+
+/// Escapes a string for C literals as UTF-8 bytes (`\xNN`).
+fn escape_c(s: &str) -> String {
+    s.bytes().map(|b| format!("\\x{b:02X}")).collect()
+}
+
+/// Escapes a string for Go and Python literals (`\UXXXXXXXX`).
+fn escape_long(s: &str) -> String {
+    s.chars().map(|c| format!("\\U{:08X}", c as u32)).collect()
+}
+
+/// Escapes a string for Rust literals (`\u{X}`).
+fn escape_rust(s: &str) -> String {
+    s.chars()
+        .map(|c| format!("\\u{{{:X}}}", c as u32))
+        .collect()
+}
+
+/// Renders the table as source code (or JSON) in the given format.
+///
+/// The header is stamped with the Unicode version the table reflects: the
+/// policy's `--unicode-version` if set, otherwise that of `runefix-core`.
+fn render(table: &WidthTable, format: TableFormat, policy: &LayoutPolicy) -> String {
+    let (major, minor, patch) = match policy.unicode_version {
+        Some(v) => (v.major, v.minor, 0),
+        None => UNICODE_VERSION,
+    };
+    let unicode = format!("{major}.{minor}.{patch}");
+    let policy = &policy.name;
+
+    if let TableFormat::Json = format {
+        let ranges: Vec<_> = table
+            .ranges
+            .iter()
+            .map(|&(a, b, w)| json!([a, b, w]))
+            .collect();
+        let sequences: Vec<_> = table
+            .sequences
+            .iter()
+            .map(|(s, w)| {
+                let cps: Vec<u32> = s.chars().map(u32::from).collect();
+                json!({ "text": s, "code_points": cps, "width": w })
+            })
+            .collect();
+        return format!(
+            "{:#}\n",
+            json!({
+                "generator": format!("runefix {CLI_VERSION}"),
+                "policy": policy,
+                "unicode_version": unicode,
+                "default_width": DEFAULT_WIDTH,
+                "ranges": ranges,
+                "sequences": sequences,
+            })
+        );
+    }
+
+    let comment = match format {
+        TableFormat::Python => "#",
+        _ => "//",
+    };
+    let mut out = String::new();
+    for line in [
+        format!(
+            "Code generated by runefix {CLI_VERSION} (policy: {policy}, Unicode {unicode}). DO NOT EDIT."
+        ),
+        "Look up a grapheme in SEQUENCES first; otherwise use the width of its".to_string(),
+        format!("first code point from WIDTH_RANGES (default {DEFAULT_WIDTH})."),
+    ] {
+        out.push_str(&format!("{comment} {line}\n"));
+    }
+    out.push('\n');
+
+    match format {
+        TableFormat::C => {
+            out.push_str("#include <stdint.h>\n\n");
+            out.push_str(&format!(
+                "#define RUNEFIX_DEFAULT_WIDTH {DEFAULT_WIDTH}\n\n"
+            ));
+            out.push_str(
+                "static const struct { uint32_t first, last; uint8_t width; } RUNEFIX_WIDTH_RANGES[] = {\n",
+            );
+            for &(a, b, w) in &table.ranges {
+                out.push_str(&format!("    {{0x{a:04X}, 0x{b:04X}, {w}}},\n"));
+            }
+            out.push_str("};\n\n");
+            out.push_str(
+                "static const struct { const char *text; uint8_t width; } RUNEFIX_SEQUENCES[] = {\n",
+            );
+            for (s, w) in &table.sequences {
+                out.push_str(&format!("    {{\"{}\", {w}}},\n", escape_c(s)));
+            }
+            out.push_str("};\n");
+        }
+        TableFormat::Rust => {
+            out.push_str(&format!(
+                "pub const DEFAULT_WIDTH: u8 = {DEFAULT_WIDTH};\n\n"
+            ));
+            out.push_str("pub const WIDTH_RANGES: &[(u32, u32, u8)] = &[\n");
+            for &(a, b, w) in &table.ranges {
+                out.push_str(&format!("    (0x{a:04X}, 0x{b:04X}, {w}),\n"));
+            }
+            out.push_str("];\n\n");
+            out.push_str("pub const SEQUENCES: &[(&str, u8)] = &[\n");
+            for (s, w) in &table.sequences {
+                out.push_str(&format!("    (\"{}\", {w}),\n", escape_rust(s)));
+            }
+            out.push_str("];\n");
+        }
+        TableFormat::Go => {
+            out.push_str("package runefix\n\n");
+            out.push_str(&format!("const DefaultWidth = {DEFAULT_WIDTH}\n\n"));
+            out.push_str(
+                "var WidthRanges = []struct {\n\tFirst, Last rune\n\tWidth       uint8\n}{\n",
+            );
+            for &(a, b, w) in &table.ranges {
+                out.push_str(&format!("\t{{0x{a:04X}, 0x{b:04X}, {w}}},\n"));
+            }
+            out.push_str("}\n\n");
+            out.push_str("var Sequences = map[string]uint8{\n");
+            for (s, w) in &table.sequences {
+                out.push_str(&format!("\t\"{}\": {w},\n", escape_long(s)));
+            }
+            out.push_str("}\n");
+        }
+        TableFormat::Python => {
+            out.push_str(&format!("DEFAULT_WIDTH = {DEFAULT_WIDTH}\n\n"));
+            out.push_str("WIDTH_RANGES = (\n");
+            for &(a, b, w) in &table.ranges {
+                out.push_str(&format!("    (0x{a:04X}, 0x{b:04X}, {w}),\n"));
+            }
+            out.push_str(")\n\n");
+            out.push_str("SEQUENCES = {\n");
+            for (s, w) in &table.sequences {
+                out.push_str(&format!("    \"{}\": {w},\n", escape_long(s)));
+            }
+            out.push_str("}\n");
+        }
+        TableFormat::Json => unreachable!("handled above"),
+    }
+
+    out
+}
+
+/// Generate a width table for `policy` and print it (or write it to `output`).
+///
+/// # Arguments
+/// * `out` - Output writer.
+/// * `policy` - Layout policy the table is derived from.
+/// * `format` - Target language of the table.
+/// * `output` - Optional file to write instead of stdout.
+pub fn run_export_table(
+    out: &mut dyn Write,
+    policy: &LayoutPolicy,
+    format: TableFormat,
+    output: Option<&Path>,
+) -> Result<()> {
+    let table = build_table(policy);
+    let rendered = render(&table, format, policy);
+
+    match output {
+        Some(path) => {
+            fs::write(path, rendered)?;
+            writeln!(
+                out,
+                " ✅ {} ranges, {} sequences → \"{}\"",
+                table.ranges.len(),
+                table.sequences.len(),
+                path.display()
+            )?;
+        }
+        None => write!(out, "{rendered}")?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+//! Execute the `flatten` command: replay carriage returns, backspaces and
+//! erase-in-line sequences to recover the final visible text of each line.
+
+use crate::commands::overstrike::flatten_line;
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::tabs::TabStops;
+use anyhow::Result;
+use std::io::Write;
+
+/// Print the text each input line would finally show on a terminal.
+///
+/// Useful for progress-bar logs captured from CI, where `\r` rewrites pile up
+/// on a single line. Wide characters are overwritten cell by cell.
+///
+/// # Arguments
+/// * `out` - Output writer.
+/// * `input` - Captured output (processed line by line).
+/// * `stops` - Tab stops used to place the cursor after a tab.
+/// * `policy` - Display width strategy.
+pub fn run_flatten(
+    out: &mut dyn Write,
+    input: &str,
+    stops: &TabStops,
+    policy: LayoutPolicy,
+) -> Result<()> {
+    for line in input.lines() {
+        writeln!(out, "{}", flatten_line(line, stops, &policy))?;
+    }
+
+    Ok(())
+}
@@ -1,9 +1,18 @@
 //! Execute the `graphemes` command: list all grapheme clusters and their widths.
 
+use super::atoms::segment_role;
+use super::report::{ReportRow, print_visual_report};
+use crate::commands::output::{OutputFormat, print_report, print_segment_table, segments};
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::samples::classify;
 use crate::config::Context;
 use crate::i18n::keys::TitleKey;
-use crate::style::{consts::WIDTH_LINE, print::*};
-use runefix_core::RuneDisplayWidth;
+use crate::style::frame::Align;
+use crate::style::print::*;
+use crate::style::table::Table;
+use anyhow::Result;
+use serde_json::json;
+use std::io::Write;
 use unicode_segmentation::UnicodeSegmentation;
 
 /// Segment input into grapheme clusters and display their visual widths.
@@ -14,36 +23,69 @@ use unicode_segmentation::UnicodeSegmentation;
 ///
 /// # Arguments
 /// * `ctx` - Global application context (used for i18n and theming).
+/// * `out` - Output writer.
 /// * `input` - Input string to be analyzed.
-pub fn run_graphemes(ctx: &Context, input: &str) {
-    // Display localized section title
-    print_title(&ctx.t(TitleKey::GraphemeClusters));
-
+/// * `policy` - Width policy used to measure each cluster.
+/// * `format` - Output format (`text`, `json`, `ndjson`, `csv`, `tsv`, `html`, `svg`).
+pub fn run_graphemes(
+    ctx: &Context,
+    out: &mut dyn Write,
+    input: &str,
+    policy: &LayoutPolicy,
+    format: OutputFormat,
+) -> Result<()> {
     // Segment into grapheme clusters
     let graphemes: Vec<&str> = input.graphemes(true).collect();
 
-    // Compute maximum display width for alignment
-    let max_width = graphemes.iter().map(|g| g.width()).max().unwrap_or(1);
+    // Structured output: one object per grapheme
+    if format.is_structured() {
+        let items = segments(input, graphemes.iter().map(|g| (*g, policy.width(g))));
+        let fields = json!({
+            "command": "graphemes",
+            "text": input,
+            "count": graphemes.len(),
+        });
+        print_report(out, format, fields, Some(("graphemes", items)))?;
+        return Ok(());
+    }
 
-    // Print header
-    println!(
-        " {:<4}   {:<width$}    Width",
-        "No.",
-        "Grapheme",
-        width = max_width
-    );
-    println!("{}", "─".repeat(WIDTH_LINE));
+    // Tabular output: one row per grapheme
+    if format.is_tabular() {
+        print_segment_table(out, format, graphemes.iter().map(|g| (*g, policy.width(g))))?;
+        return Ok(());
+    }
+
+    // Visual report: cell grid, category hints and legend
+    if format.is_visual() {
+        let rows: Vec<ReportRow> = graphemes
+            .iter()
+            .map(|g| ReportRow {
+                text: g,
+                width: policy.width(g),
+                hint: classify(g).map(|c| c.label()),
+            })
+            .collect();
+        let title = ctx.t(TitleKey::GraphemeClusters);
+        print_visual_report(ctx, out, format, &title, input, None, &rows)?;
+        return Ok(());
+    }
+
+    // Display localized section title
+    print_title(out, &ctx.t(TitleKey::GraphemeClusters))?;
 
     // Print each grapheme cluster with its width
+    let mut table = Table::new(["No.", "Grapheme", "Width"]).align(2, Align::Right);
     for (i, g) in graphemes.iter().enumerate() {
-        let visual_width = g.width();
-        let pad = max_width.saturating_sub(visual_width);
-        let padded = format!("{g}{}", " ".repeat(pad));
-        println!(" {:02}     {}          {}", i, padded, visual_width);
+        let visual_width = policy.width(g);
+        let cells = vec![format!("{i:02}"), g.to_string(), visual_width.to_string()];
+        table.styled_row(cells, segment_role(g, visual_width));
     }
 
     // Add bottom border if many entries
     if graphemes.len() >= 5 {
-        println!("{}", "─".repeat(WIDTH_LINE));
+        table.divider();
     }
+    table.print(out, &ctx.theme)?;
+
+    Ok(())
 }
@@ -1,14 +1,17 @@
 //! Interactive initializer for runefix CLI.
 
-use crate::config::Config;
+use crate::config::{Config, Context};
+use crate::error::RunefixError;
+use crate::i18n::keys::ErrorKey;
 use crate::i18n::lang::Lang;
 use anyhow::Result;
 use inquire::Select;
 use std::fs;
+use std::io::Write;
 
 /// Run the `init` command: generate a config file at XDG-compliant location,
 /// by interactively selecting a preferred language.
-pub fn run_init() -> Result<()> {
+pub fn run_init(ctx: &Context, out: &mut dyn Write) -> Result<()> {
     // Define language options
     let options = [
         ("🇺🇸 English (en-US)", Lang::EnUS),
@@ -26,11 +29,19 @@ pub fn run_init() -> Result<()> {
 
     let selected_lang = options.iter().find(|x| x.0 == selected).unwrap().1;
 
-    // Build config content
-    let content = format!("lang = \"{}\"\n", selected_lang.code());
-
     // Get XDG-compatible config path
-    let path = Config::config_path();
+    let path =
+        Config::config_path().ok_or_else(|| RunefixError::new(ctx, ErrorKey::ConfigPathUnknown))?;
+
+    // Build config content, keeping everything but the old `lang` line
+    // (e.g. `[policy.<name>]` sections)
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let rest: String = existing
+        .lines()
+        .filter(|l| l.split_once('=').is_none_or(|(k, _)| k.trim() != "lang"))
+        .map(|l| format!("{l}\n"))
+        .collect();
+    let content = format!("lang = \"{}\"\n{rest}", selected_lang.code());
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?; // ensure parent dirs exist
     }
@@ -45,6 +56,6 @@ pub fn run_init() -> Result<()> {
         Lang::JaJP => "言語を保存しました",
     };
 
-    println!("\n✅  {} \"{}\"\n", msg, path.display());
+    writeln!(out, "\n✅  {} \"{}\"\n", msg, path.display())?;
     Ok(())
 }
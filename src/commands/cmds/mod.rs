@@ -1,20 +1,47 @@
 pub use atoms::run_atoms;
+pub use boxed::run_box;
+pub use compare::run_compare;
+pub use expand::run_expand;
+pub use export_table::run_export_table;
+pub use flatten::run_flatten;
 pub use graphemes::run_graphemes;
 pub use init::run_init;
+pub use policy_diff::run_policy_diff;
+pub use probe::run_probe;
+pub use ruler::run_ruler;
+pub use screen::run_screen;
+pub use show::run_show;
 pub use slice::run_slice;
 pub use split::run_split;
+pub use testpattern::run_testpattern;
 pub use truncate::run_truncate;
+pub use unexpand::run_unexpand;
+pub use unicode_diff::run_unicode_diff;
 pub use version::run_version;
 pub use width::run_width;
 pub use widths::run_widths;
 
+pub mod compare;
+pub mod export_table;
+pub mod ruler;
 pub mod slice;
 
 mod atoms;
+mod boxed;
+mod expand;
+mod flatten;
 mod graphemes;
 mod init;
+mod policy_diff;
+mod probe;
+mod report;
+mod screen;
+mod show;
 mod split;
+mod testpattern;
 mod truncate;
+mod unexpand;
+mod unicode_diff;
 mod version;
 mod width;
 mod widths;
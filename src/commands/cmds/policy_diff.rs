@@ -0,0 +1,81 @@
+//! Execute the `policy diff` command: list graphemes whose width differs
+//! between two or more width policies.
+
+use crate::commands::output::code_points;
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::samples::collect_candidates;
+use crate::config::Context;
+use crate::i18n::format_i18n;
+use crate::i18n::keys::{FooterKey, TitleKey};
+use crate::style::print::*;
+use crate::style::table::Table;
+use anyhow::Result;
+use std::io::Write;
+
+/// Compare per-grapheme widths across policies and print every disagreement.
+///
+/// Graphemes come from `input` when given (each distinct grapheme is listed
+/// once); otherwise all code points plus common emoji sequences are scanned.
+///
+/// # Arguments
+/// * `ctx` - Global application context.
+/// * `out` - Output writer.
+/// * `policies` - Policies to compare (at least two).
+/// * `input` - Optional text to segment.
+/// * `verbose` - Enable verbose output (title).
+pub fn run_policy_diff(
+    ctx: &Context,
+    out: &mut dyn Write,
+    policies: &[LayoutPolicy],
+    input: Option<&str>,
+    verbose: bool,
+) -> Result<()> {
+    // Optional header (only in verbose mode)
+    if verbose {
+        print_title(out, &ctx.t(TitleKey::PolicyDiff))?;
+    }
+
+    let candidates = collect_candidates(input);
+
+    let mut headers = vec!["Grapheme".to_string(), "Code Points".to_string()];
+    headers.extend(policies.iter().map(|p| p.name.clone()));
+    let mut table = Table::new(headers);
+
+    // Rows: only graphemes where at least two policies disagree
+    let mut differing = 0;
+    let mut totals = vec![0; policies.len()];
+    for g in &candidates {
+        let widths: Vec<usize> = policies.iter().map(|p| p.width(g)).collect();
+        if widths.iter().all(|&w| w == widths[0]) {
+            continue;
+        }
+
+        differing += 1;
+        let mut cells = vec![g.to_string(), code_points(g).join(" ")];
+        for (i, w) in widths.iter().enumerate() {
+            totals[i] += w;
+            cells.push(w.to_string());
+        }
+        table.row(cells);
+    }
+
+    // Totals over the listed graphemes
+    table.divider();
+    table.span_row(
+        ctx.t(FooterKey::PolicyDiffTotal),
+        2,
+        totals.iter().map(|t| t.to_string()).collect(),
+    );
+    table.print(out, &ctx.theme)?;
+
+    let summary = format_i18n(
+        &ctx.t(FooterKey::PolicyDiffSummary),
+        &[
+            ("diff", differing.to_string()),
+            ("total", candidates.len().to_string()),
+        ],
+    );
+    writeln!(out, " {summary}")?;
+
+    Ok(())
+}
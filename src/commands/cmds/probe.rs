@@ -0,0 +1,336 @@
+//! Execute the `probe` command: measure how the real terminal renders sample
+//! graphemes via cursor position reports (`CSI 6n`), and compare the results
+//! with the built-in width policies.
+
+use crate::commands::policy::{BUILTIN_POLICIES, LayoutPolicy, parse_policy};
+use crate::commands::samples::{SAMPLES, Sample, SampleCategory};
+use crate::commands::tabs::TabStops;
+use crate::commands::vt::Screen;
+use crate::config::Context;
+use crate::error::RunefixError;
+use crate::i18n::keys::{ErrorKey, FooterKey, TitleKey};
+use crate::style::print::*;
+use crate::style::table::Table;
+use anyhow::{Result, anyhow};
+use runefix_core::WidthPolicy;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// A terminal that can report how many columns a grapheme occupied.
+trait ProbeTerminal {
+    /// Writes `text` at column 0 and returns the 1-based cursor column afterwards.
+    fn write_and_query(&mut self, text: &str) -> Result<usize>;
+}
+
+/// Parses a cursor position report (`ESC [ row ; col R`) into `(row, col)`.
+fn parse_cursor_report(reply: &str) -> Option<(usize, usize)> {
+    let start = reply.rfind("\u{1B}[")?;
+    let body = reply[start + 2..].strip_suffix('R')?;
+    let (row, col) = body.split_once(';')?;
+    Some((row.parse().ok()?, col.parse().ok()?))
+}
+
+/// Stand-in terminal backed by the virtual screen, answering queries itself.
+///
+/// Useful for testing, or for previewing what a given policy would report.
+struct EmulatedTerminal<'p> {
+    screen: Screen<'p>,
+}
+
+impl ProbeTerminal for EmulatedTerminal<'_> {
+    fn write_and_query(&mut self, text: &str) -> Result<usize> {
+        self.screen.feed(&format!("\r\u{1B}[K{text}\u{1B}[6n"));
+        let reply = self.screen.take_responses();
+        parse_cursor_report(&reply)
+            .map(|(_, col)| col)
+            .ok_or_else(|| anyhow!("invalid cursor position report: {reply:?}"))
+    }
+}
+
+/// The controlling terminal (`/dev/tty`), switched to raw mode while probing.
+#[cfg(unix)]
+struct TtyTerminal {
+    tty: fs::File,
+    saved: libc::termios,
+}
+
+#[cfg(unix)]
+impl TtyTerminal {
+    /// Opens `/dev/tty` and enables raw mode with a short read timeout.
+    fn open() -> Option<Self> {
+        use std::os::fd::AsRawFd;
+
+        let tty = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open("/dev/tty")
+            .ok()?;
+        let fd = tty.as_raw_fd();
+
+        // SAFETY: `fd` is a valid open terminal descriptor for the lifetime of `tty`,
+        // and `termios` is plain data initialized by `tcgetattr`.
+        unsafe {
+            let mut saved: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(fd, &mut saved) != 0 {
+                return None;
+            }
+            let mut raw = saved;
+            libc::cfmakeraw(&mut raw);
+            raw.c_cc[libc::VMIN] = 0;
+            raw.c_cc[libc::VTIME] = 10; // 1s timeout per read
+            if libc::tcsetattr(fd, libc::TCSANOW, &raw) != 0 {
+                return None;
+            }
+            Some(TtyTerminal { tty, saved })
+        }
+    }
+}
+
+#[cfg(unix)]
+impl ProbeTerminal for TtyTerminal {
+    fn write_and_query(&mut self, text: &str) -> Result<usize> {
+        use std::io::{Read, Write};
+
+        write!(self.tty, "\r\u{1B}[K{text}\u{1B}[6n")?;
+        self.tty.flush()?;
+
+        // Read until the report's terminating `R` (or time out)
+        let mut reply = Vec::new();
+        let mut byte = [0u8; 1];
+        while !reply.ends_with(b"R") {
+            if self.tty.read(&mut byte)? == 0 {
+                return Err(anyhow!("timeout"));
+            }
+            reply.push(byte[0]);
+        }
+
+        let reply = String::from_utf8_lossy(&reply);
+        parse_cursor_report(&reply)
+            .map(|(_, col)| col)
+            .ok_or_else(|| anyhow!("invalid cursor position report: {reply:?}"))
+    }
+}
+
+#[cfg(unix)]
+impl Drop for TtyTerminal {
+    fn drop(&mut self) {
+        use std::io::Write;
+        use std::os::fd::AsRawFd;
+
+        // Clear the probe line and restore the original terminal mode
+        let _ = write!(self.tty, "\r\u{1B}[K");
+        let _ = self.tty.flush();
+        // SAFETY: restores the settings captured in `open` on the same descriptor.
+        unsafe {
+            libc::tcsetattr(self.tty.as_raw_fd(), libc::TCSANOW, &self.saved);
+        }
+    }
+}
+
+/// Opens the controlling terminal, if the platform and environment allow it.
+fn open_tty() -> Option<Box<dyn ProbeTerminal>> {
+    #[cfg(unix)]
+    {
+        TtyTerminal::open().map(|t| Box::new(t) as Box<dyn ProbeTerminal>)
+    }
+    #[cfg(not(unix))]
+    {
+        None
+    }
+}
+
+/// Derives a policy from measured widths of representative samples.
+fn derive_policy(results: &[(&Sample, usize)]) -> WidthPolicy {
+    let first = |cat: SampleCategory, default: usize| {
+        results
+            .iter()
+            .find(|(s, _)| s.category == cat)
+            .map_or(default, |(_, w)| *w)
+    };
+
+    WidthPolicy {
+        emoji: first(SampleCategory::Emoji, 2),
+        cjk: first(SampleCategory::Cjk, 2),
+        variant: first(SampleCategory::Fullwidth, 2),
+        fallback: 1,
+    }
+}
+
+/// Comment line written above every saved section.
+const SAVED_COMMENT: &str = "# Derived by `runefix probe`";
+
+/// Returns the policy name of a `[policy.<name>]` header line, if it is one.
+fn policy_header(line: &str) -> Option<&str> {
+    let header = line.trim().strip_prefix('[')?.strip_suffix(']')?;
+    let name = header.trim().strip_prefix("policy.")?;
+    Some(name.trim().trim_matches('"'))
+}
+
+/// Writes a derived policy as a named config section.
+///
+/// The rest of the file (language, theme, other sections) is kept: an
+/// existing `[policy.<name>]` section is replaced in place, otherwise the
+/// section is appended. The file is written to a temporary sibling first and
+/// renamed into place, so an interrupted save leaves the old config intact.
+fn save_policy(path: &Path, name: &str, policy: &WidthPolicy) -> Result<()> {
+    let section = format!(
+        "{SAVED_COMMENT}\n[policy.{name}]\nemoji = {}\ncjk = {}\nvariant = {}\nfallback = {}\n",
+        policy.emoji, policy.cjk, policy.variant, policy.fallback
+    );
+
+    let existing = fs::read_to_string(path).unwrap_or_default();
+    let mut content = String::new();
+    let mut replaced = false;
+    let mut skipping = false;
+    // Blank and comment lines at the end of a replaced section belong to
+    // whatever follows it, so they are only kept if another section does
+    let mut trailing = String::new();
+    for line in existing.lines() {
+        if line.trim_start().starts_with('[') {
+            if skipping {
+                content.push_str(&trailing);
+            }
+            trailing.clear();
+            skipping = policy_header(line) == Some(name);
+            if skipping && !replaced {
+                // Drop the comment left by an earlier save of this section
+                if content.ends_with(&format!("{SAVED_COMMENT}\n")) {
+                    content.truncate(content.len() - SAVED_COMMENT.len() - 1);
+                }
+                content.push_str(&section);
+                replaced = true;
+            }
+        }
+        let trimmed = line.trim();
+        if !skipping {
+            content.push_str(line);
+            content.push('\n');
+        } else if trimmed.is_empty() || trimmed.starts_with('#') {
+            trailing.push_str(line);
+            trailing.push('\n');
+        } else {
+            trailing.clear();
+        }
+    }
+    if !replaced {
+        if !content.is_empty() && !content.ends_with("\n\n") {
+            content.push('\n');
+        }
+        content.push_str(&section);
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Probe the terminal's actual rendering width of each sample grapheme.
+///
+/// Each sample is written at column 0, followed by a cursor position query;
+/// the reported column gives the width the terminal really used. The report
+/// lists every sample where a built-in policy disagrees.
+///
+/// # Arguments
+/// * `ctx` - Global application context.
+/// * `out` - Output writer.
+/// * `emulate` - Answer queries from a virtual terminal using this policy
+///   instead of the real terminal (for testing).
+/// * `save` - Optional path to write a derived policy to.
+/// * `name` - Section name for the saved policy.
+/// * `verbose` - Enable verbose output (title, every sample row).
+pub fn run_probe(
+    ctx: &Context,
+    out: &mut dyn Write,
+    emulate: Option<LayoutPolicy>,
+    save: Option<&Path>,
+    name: &str,
+    verbose: bool,
+) -> Result<()> {
+    // Measure every sample first, so the report is printed after raw mode ends
+    let results = {
+        let mut term: Box<dyn ProbeTerminal + '_> = match &emulate {
+            Some(policy) => Box::new(EmulatedTerminal {
+                screen: Screen::new(80, 1, TabStops::default(), policy),
+            }),
+            None => open_tty()
+                .ok_or_else(|| anyhow!(RunefixError::new(ctx, ErrorKey::ProbeNoTerminal)))?,
+        };
+
+        let mut results = Vec::with_capacity(SAMPLES.len());
+        for sample in SAMPLES {
+            let col = term
+                .write_and_query(sample.text)
+                .map_err(|_| anyhow!(RunefixError::new(ctx, ErrorKey::ProbeNoReply)))?;
+            results.push((sample, col.saturating_sub(1)));
+        }
+        results
+    };
+
+    if verbose {
+        print_title(out, &ctx.t(TitleKey::TerminalProbe))?;
+    }
+
+    let policies: Vec<(&str, LayoutPolicy)> = BUILTIN_POLICIES
+        .iter()
+        .map(|&n| parse_policy(ctx, n).map(|p| (n, p)))
+        .collect::<Result<_, _>>()?;
+    let mut headers = vec!["Grapheme", "Category", "Actual"];
+    headers.extend(policies.iter().map(|(n, _)| *n));
+    let mut table = Table::new(headers);
+
+    // Rows: every sample in verbose mode, otherwise only disagreements
+    let mut mismatches = vec![0; policies.len()];
+    for (sample, actual) in &results {
+        let mut cells = vec![
+            sample.text.to_string(),
+            sample.category.label().to_string(),
+            actual.to_string(),
+        ];
+        let mut differs = false;
+        for (i, (_, p)) in policies.iter().enumerate() {
+            let expected = p.display_width(sample.text);
+            if expected == *actual {
+                cells.push(expected.to_string());
+            } else {
+                mismatches[i] += 1;
+                differs = true;
+                cells.push(format!("{expected} ✗"));
+            }
+        }
+        if differs || verbose {
+            table.row(cells);
+        }
+    }
+
+    // Totals and best match
+    table.divider();
+    table.span_row(
+        ctx.t(FooterKey::ProbeMismatches),
+        3,
+        mismatches.iter().map(|m| m.to_string()).collect(),
+    );
+    table.print(out, &ctx.theme)?;
+
+    let best = mismatches
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, m)| **m)
+        .map(|(i, _)| policies[i].0)
+        .unwrap_or("terminal");
+    writeln!(out, " {}: {best}", ctx.t(FooterKey::ProbeBestMatch))?;
+
+    // Optionally save a derived custom policy
+    if let Some(path) = save {
+        let derived = derive_policy(&results);
+        save_policy(path, name, &derived)?;
+        writeln!(out, " ✅ [policy.{name}] → \"{}\"", path.display())?;
+    }
+
+    Ok(())
+}
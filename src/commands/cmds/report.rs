@@ -0,0 +1,304 @@
+//! Self-contained HTML and SVG reports for `atoms` and `graphemes`.
+//!
+//! Reports lay the input out on a cell grid (one box per segment, as wide as
+//! its display width, with zero-width segments drawn as markers), followed by
+//! a table of code points, hints and widths and the localized legend. They
+//! embed their styles and need no fonts or scripts beyond the browser's own.
+
+use super::atoms::GLOSSARY;
+use super::show::escape_control;
+use crate::commands::output::{OutputFormat, code_points};
+use crate::config::Context;
+use crate::i18n::keys::FooterKey;
+use std::io::{self, Write};
+
+/// Width and height of one grid cell in the SVG report, in pixels.
+const SVG_CELL: usize = 28;
+const SVG_ROW: usize = 48;
+
+/// Left margin and line height of the SVG report, in pixels.
+const SVG_MARGIN: usize = 16;
+const SVG_LINE: usize = 22;
+
+/// X offsets of the SVG table columns (No., Rune, Width, Hint, Code Points).
+const SVG_COLUMNS: [usize; 5] = [16, 60, 120, 180, 330];
+
+/// One segment of the report.
+pub(super) struct ReportRow<'a> {
+    /// Segment text
+    pub text: &'a str,
+
+    /// Display width in columns
+    pub width: usize,
+
+    /// Semantic label (e.g. `ZWJ`, `Skin Tone`), if any
+    pub hint: Option<&'static str>,
+}
+
+/// Escapes `&`, `<`, `>` and quotes for HTML/XML, and replaces control
+/// characters (invalid in XML) with visible escapes such as `\x07`.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c if c.is_control() => out.push_str(&escape_control(c)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Returns the visible form of a segment: escapes for controls, and a dotted
+/// circle carrier for zero-width segments (joiners, selectors, marks).
+fn glyph(row: &ReportRow) -> String {
+    if row.text.chars().all(char::is_control) {
+        escape(row.text)
+    } else if row.width == 0 {
+        format!("◌{}", escape(row.text))
+    } else {
+        escape(row.text)
+    }
+}
+
+/// Returns the CSS class (and SVG fill) of a segment's grid cell.
+fn cell_class(row: &ReportRow) -> &'static str {
+    if row.text.chars().any(char::is_control) {
+        "control"
+    } else if row.width == 0 {
+        "zero"
+    } else if row.width >= 2 {
+        "wide"
+    } else {
+        "narrow"
+    }
+}
+
+/// Returns the number of grid columns a segment's cell spans (controls get
+/// at least one so their escape stays visible).
+fn cell_span(row: &ReportRow) -> usize {
+    if cell_class(row) == "control" {
+        row.width.max(1)
+    } else {
+        row.width
+    }
+}
+
+/// Prints an HTML or SVG report of the segments of `input`.
+///
+/// # Arguments
+/// * `ctx` - Global context (legend and total labels, document language).
+/// * `out` - Output writer.
+/// * `format` - `html` or `svg`.
+/// * `title` - Report heading (e.g. the command's localized title).
+/// * `input` - The analyzed text.
+/// * `policy` - Name of the width policy, if the command uses one.
+/// * `rows` - Segments in order.
+pub(super) fn print_visual_report(
+    ctx: &Context,
+    out: &mut dyn Write,
+    format: OutputFormat,
+    title: &str,
+    input: &str,
+    policy: Option<&str>,
+    rows: &[ReportRow],
+) -> io::Result<()> {
+    let total: usize = rows.iter().map(|r| r.width).sum();
+    let mut meta = format!("{}: {}", ctx.t(FooterKey::AtomsDetail), total);
+    if let Some(policy) = policy {
+        meta = format!("policy: {policy} · {meta}");
+    }
+
+    match format {
+        OutputFormat::Svg => write!(out, "{}", render_svg(ctx, title, &meta, rows)),
+        _ => write!(out, "{}", render_html(ctx, title, &meta, input, rows)),
+    }
+}
+
+/// Renders the report as a standalone HTML document.
+fn render_html(ctx: &Context, title: &str, meta: &str, input: &str, rows: &[ReportRow]) -> String {
+    let mut grid = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        grid.push_str(&format!(
+            "<div class=\"cell {}\" style=\"--w: {}\" title=\"{}\">{}<small>{}</small></div>\n",
+            cell_class(row),
+            row.width,
+            code_points(row.text).join(" "),
+            glyph(row),
+            i
+        ));
+    }
+
+    let mut table = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        table.push_str(&format!(
+            "<tr><td>{:02}</td><td class=\"rune\">{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            i,
+            glyph(row),
+            row.width,
+            row.hint.unwrap_or(""),
+            code_points(row.text).join(" ")
+        ));
+    }
+
+    let legend: String = GLOSSARY
+        .iter()
+        .map(|line| format!("<li>{}</li>\n", escape(line)))
+        .collect();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="{lang}">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ font-family: system-ui, sans-serif; margin: 2em; color: #111827; }}
+.meta {{ color: #6b7280; }}
+pre {{ background: #f9fafb; padding: .5em; white-space: pre-wrap; }}
+.grid {{ display: flex; flex-wrap: wrap; margin: 1em 0; font-family: ui-monospace, monospace; }}
+.cell {{ box-sizing: border-box; width: calc(var(--w) * 1.75em); height: 3.2em; border: 1px solid #9ca3af;
+  display: flex; flex-direction: column; align-items: center; justify-content: center; font-size: 1.1em; }}
+.cell small {{ font-size: .55em; color: #6b7280; }}
+.wide {{ background: #dbeafe; }}
+.narrow {{ background: #f3f4f6; }}
+.zero {{ width: .7em; background: #f5d0fe; border-color: #c026d3; font-size: .6em; overflow: hidden; }}
+.control {{ width: calc(max(var(--w), 1) * 1.75em); background: #fee2e2; font-size: .7em; }}
+table {{ border-collapse: collapse; }}
+th, td {{ border-bottom: 1px solid #e5e7eb; padding: .25em .75em; text-align: left; }}
+td.rune {{ font-size: 1.2em; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<p class="meta">{meta}</p>
+<pre>{input}</pre>
+<div class="grid">
+{grid}</div>
+<table>
+<thead><tr><th>No.</th><th>Rune</th><th>Width</th><th>Hint</th><th>Code Points</th></tr></thead>
+<tbody>
+{table}</tbody>
+</table>
+<h2>{legend_title}</h2>
+<ul>
+{legend}</ul>
+</body>
+</html>
+"#,
+        lang = ctx.lang.code(),
+        title = escape(title),
+        meta = escape(meta),
+        input = escape(input),
+        legend_title = escape(&ctx.t(FooterKey::LegendGlossary)),
+    )
+}
+
+/// Renders the report as a standalone SVG image.
+fn render_svg(ctx: &Context, title: &str, meta: &str, rows: &[ReportRow]) -> String {
+    let columns: usize = rows.iter().map(cell_span).sum();
+    let width = (SVG_MARGIN * 2 + columns * SVG_CELL).max(640);
+
+    let mut body = String::new();
+    let mut y = 30;
+    body.push_str(&format!(
+        "<text x=\"{SVG_MARGIN}\" y=\"{y}\" class=\"title\">{}</text>\n",
+        escape(title)
+    ));
+    y += SVG_LINE;
+    body.push_str(&format!(
+        "<text x=\"{SVG_MARGIN}\" y=\"{y}\" class=\"meta\">{}</text>\n",
+        escape(meta)
+    ));
+
+    // Cell grid: zero-width segments are markers on the boundary they attach to
+    y += 14;
+    let mut x = SVG_MARGIN;
+    for (i, row) in rows.iter().enumerate() {
+        let class = cell_class(row);
+        if class == "zero" {
+            body.push_str(&format!(
+                "<line x1=\"{x}\" y1=\"{}\" x2=\"{x}\" y2=\"{}\" class=\"zero\"><title>{:02} {}</title></line>\n",
+                y - 4,
+                y + SVG_ROW + 4,
+                i,
+                code_points(row.text).join(" ")
+            ));
+            continue;
+        }
+
+        let w = cell_span(row) * SVG_CELL;
+        let cx = x + w / 2;
+        body.push_str(&format!(
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{SVG_ROW}\" class=\"{class}\"/>\n\
+             <text x=\"{cx}\" y=\"{}\" class=\"glyph\">{}</text>\n\
+             <text x=\"{cx}\" y=\"{}\" class=\"index\">{i}</text>\n",
+            y + 27,
+            glyph(row),
+            y + 42
+        ));
+        x += w;
+    }
+    y += SVG_ROW + 30;
+
+    // Detail table
+    let header = ["No.", "Rune", "Width", "Hint", "Code Points"];
+    for (col, label) in SVG_COLUMNS.iter().zip(header) {
+        body.push_str(&format!(
+            "<text x=\"{col}\" y=\"{y}\" class=\"head\">{label}</text>\n"
+        ));
+    }
+    for (i, row) in rows.iter().enumerate() {
+        y += SVG_LINE;
+        let cells = [
+            format!("{i:02}"),
+            glyph(row),
+            row.width.to_string(),
+            row.hint.unwrap_or("").to_string(),
+            code_points(row.text).join(" "),
+        ];
+        for (col, cell) in SVG_COLUMNS.iter().zip(cells) {
+            body.push_str(&format!("<text x=\"{col}\" y=\"{y}\">{cell}</text>\n"));
+        }
+    }
+
+    // Legend
+    y += SVG_LINE * 2;
+    body.push_str(&format!(
+        "<text x=\"{SVG_MARGIN}\" y=\"{y}\" class=\"head\">{}</text>\n",
+        escape(&ctx.t(FooterKey::LegendGlossary))
+    ));
+    for line in GLOSSARY {
+        y += SVG_LINE;
+        body.push_str(&format!(
+            "<text x=\"{SVG_MARGIN}\" y=\"{y}\">{}</text>\n",
+            escape(line)
+        ));
+    }
+    let height = y + SVG_MARGIN;
+
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="ui-monospace, monospace" font-size="14">
+<title>{title}</title>
+<style>
+.title {{ font: bold 18px system-ui, sans-serif; }}
+.meta {{ fill: #6b7280; }}
+.head {{ font-weight: bold; }}
+.glyph {{ font-size: 20px; text-anchor: middle; }}
+.index {{ font-size: 10px; fill: #6b7280; text-anchor: middle; }}
+rect {{ stroke: #9ca3af; }}
+.wide {{ fill: #dbeafe; }}
+.narrow {{ fill: #f3f4f6; }}
+.control {{ fill: #fee2e2; }}
+.zero {{ stroke: #c026d3; stroke-width: 3; }}
+</style>
+<rect width="100%" height="100%" fill="white" stroke="none"/>
+{body}</svg>
+"#,
+        title = escape(title),
+    )
+}
@@ -0,0 +1,159 @@
+//! Execute the `ruler` command: overlay a column ruler on input lines
+//! to show exactly which terminal cells each grapheme occupies.
+
+use crate::commands::policy::LayoutPolicy;
+use crate::config::Context;
+use crate::i18n::keys::{FooterKey, TitleKey};
+use crate::style::{print::*, term::*};
+use anyhow::Result;
+use clap::ValueEnum;
+use std::io::Write;
+
+/// Where the ruler is drawn relative to each input line.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum RulerPosition {
+    #[default]
+    Above,
+    Below,
+    Both,
+}
+
+/// Build the numeric label row and tick row for a ruler of `width` columns.
+///
+/// Labels are placed every 5 columns (`0    5    10`) and may run past the
+/// last column; ticks use `|` on those columns and `.` elsewhere. Guide columns from `marks` are drawn as `#`.
+fn ruler_rows(width: usize, marks: &[usize]) -> (String, String) {
+    let mut labels = String::new();
+    let mut col = 0;
+    while col < width {
+        let label = col.to_string();
+        labels.push_str(&label);
+        col += label.len();
+
+        // Fill up to the next multiple of 5
+        let next = (col / 5 + 1) * 5;
+        let gap = next.min(width).saturating_sub(col);
+        labels.push_str(&" ".repeat(gap));
+        col += gap;
+    }
+
+    let ticks: String = (0..width)
+        .map(|c| {
+            if marks.contains(&c) {
+                '#'
+            } else if c % 5 == 0 {
+                '|'
+            } else {
+                '.'
+            }
+        })
+        .collect();
+
+    (labels.trim_end().to_string(), ticks)
+}
+
+/// Build the cell marker row for a line, plus an optional zero-width row.
+///
+/// Markers per cell:
+/// - `^` start of a 1-column grapheme
+/// - `<` … `>` cells covered by a wide grapheme
+/// - `*` (second row) position of a zero-width cluster
+fn marker_rows(line: &str, policy: &LayoutPolicy) -> (String, Option<String>) {
+    let mut cells = String::new();
+    let mut zero = String::new();
+    let mut has_zero = false;
+    let mut col = 0;
+
+    for (_, w) in policy.grapheme_widths(line) {
+        match w {
+            0 => {
+                // Zero-width cluster sits at the current column (one `*` per column)
+                if zero.len() <= col {
+                    zero.push_str(&" ".repeat(col - zero.len()));
+                    zero.push('*');
+                }
+                has_zero = true;
+            }
+            1 => cells.push('^'),
+            _ => {
+                cells.push('<');
+                cells.push_str(&"=".repeat(w - 2));
+                cells.push('>');
+            }
+        }
+        col += w;
+    }
+
+    (cells, has_zero.then_some(zero))
+}
+
+/// Print each input line together with a column ruler and cell markers.
+///
+/// # Arguments
+/// * `ctx` - Global application context.
+/// * `out` - Output writer.
+/// * `input` - Text to inspect (one ruler per line).
+/// * `marks` - Guide columns to highlight (e.g. `80`).
+/// * `position` - Whether the ruler is drawn above, below, or around the line.
+/// * `policy` - Display width strategy.
+/// * `verbose` - Enable verbose output (title and legend).
+pub fn run_ruler(
+    ctx: &Context,
+    out: &mut dyn Write,
+    input: &str,
+    marks: &[usize],
+    position: RulerPosition,
+    policy: LayoutPolicy,
+    verbose: bool,
+) -> Result<()> {
+    // Optional header (only in verbose mode)
+    if verbose {
+        print_title(out, &ctx.t(TitleKey::ColumnRuler))?;
+    }
+
+    let lines: Vec<&str> = input.lines().collect();
+
+    // Size the ruler to the widest line, or past the furthest guide column
+    let widest = lines
+        .iter()
+        .map(|l| policy.display_width(l))
+        .max()
+        .unwrap_or(0);
+    let width = marks
+        .iter()
+        .map(|m| m.saturating_add(1))
+        .fold(widest.max(1), usize::max);
+    let (labels, ticks) = ruler_rows(width, marks);
+
+    for line in lines {
+        if matches!(position, RulerPosition::Above | RulerPosition::Both) {
+            writeln!(out, "{labels}")?;
+            writeln!(out, "{ticks}")?;
+        }
+
+        writeln!(out, "{line}")?;
+
+        let (cells, zero) = marker_rows(line, &policy);
+        writeln!(out, "{cells}")?;
+        if let Some(zero) = zero {
+            writeln!(out, "{zero}")?;
+        }
+
+        if matches!(position, RulerPosition::Below | RulerPosition::Both) {
+            writeln!(out, "{ticks}")?;
+            writeln!(out, "{labels}")?;
+        }
+    }
+
+    // Legend (only in verbose mode)
+    if verbose {
+        writeln!(out)?;
+        writeln!(out, "{}:", bold(&ctx.t(FooterKey::LegendGlossary)))?;
+        writeln!(out, "^ = Narrow grapheme (1 column)")?;
+        writeln!(out, "<=> = Wide grapheme (one cell per column)")?;
+        writeln!(out, "* = Zero-width cluster")?;
+        writeln!(out, "# = Guide column (--mark)")?;
+    }
+
+    Ok(())
+}
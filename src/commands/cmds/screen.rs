@@ -0,0 +1,68 @@
+//! Execute the `screen` command: replay terminal output on a virtual screen
+//! and print the final state, for deterministic snapshot testing of TUI programs.
+
+use crate::commands::output::{OutputFormat, print_report};
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::tabs::TabStops;
+use crate::commands::vt::Screen;
+use crate::style::frame::{Align, BorderStyle, FrameOptions, align_text, render_frame};
+use anyhow::Result;
+use std::io::Write;
+
+/// Feed the input into a `cols` × `rows` virtual terminal and print the final screen.
+///
+/// # Arguments
+/// * `out` - Output writer.
+/// * `input` - Captured terminal output (text plus escape sequences).
+/// * `cols` - Screen width in columns.
+/// * `rows` - Screen height in rows.
+/// * `stops` - Tab stops used to place the cursor after a tab.
+/// * `policy` - Display width strategy used for each cluster.
+/// * `format` - Output format; `json` / `ndjson` print the screen with per-cell attributes.
+/// * `frame` - Draw a border around the screen to make its edges visible.
+#[allow(clippy::too_many_arguments)]
+pub fn run_screen(
+    out: &mut dyn Write,
+    input: &str,
+    cols: usize,
+    rows: usize,
+    stops: TabStops,
+    policy: LayoutPolicy,
+    format: OutputFormat,
+    frame: bool,
+) -> Result<()> {
+    let mut screen = Screen::new(cols, rows, stops, &policy);
+    screen.feed(input);
+
+    if format.is_structured() {
+        print_report(out, format, screen.to_json(), None)?;
+        return Ok(());
+    }
+
+    let lines = screen.lines();
+    if !frame {
+        for line in lines {
+            writeln!(out, "{line}")?;
+        }
+        return Ok(());
+    }
+
+    // Pad every row to the full screen width so the right border marks the edge
+    let padded: Vec<String> = lines
+        .iter()
+        .map(|l| align_text(l, cols, Align::Left, &policy))
+        .collect();
+    let rows: Vec<&str> = padded.iter().map(String::as_str).collect();
+    let opts = FrameOptions {
+        style: BorderStyle::Single,
+        title: None,
+        padding: 0,
+        align: Align::Left,
+        policy: &policy,
+    };
+    for row in render_frame(&rows, &opts) {
+        writeln!(out, "{row}")?;
+    }
+
+    Ok(())
+}
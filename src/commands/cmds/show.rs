@@ -0,0 +1,158 @@
+//! Execute the `show` command: render text as a grid of terminal cells,
+//! making wide characters, zero-width atoms and control characters visible.
+
+use super::atoms::{display_unicode_hint, is_combining_mark, is_emoji_vs, is_zwj};
+use super::slice::{SliceMode, split_str_units};
+use crate::commands::policy::LayoutPolicy;
+use crate::config::Context;
+use crate::i18n::keys::{FooterKey, TitleKey};
+use crate::style::{print::*, term::*};
+use anyhow::Result;
+use runefix_core::{RuneDisplayWidth, atoms};
+use std::io::Write;
+
+/// Background colors used for cell categories in colored output.
+const BG_WIDE: &str = "44";
+const BG_CONTINUATION: &str = "100";
+const BG_ZERO_WIDTH: &str = "45";
+const BG_CONTROL: &str = "41";
+
+/// Return a visible escape for a control character (e.g. `\t`, `\e`, `\x07`).
+pub(super) fn escape_control(c: char) -> String {
+    match c {
+        '\t' => "\\t".to_string(),
+        '\r' => "\\r".to_string(),
+        '\n' => "\\n".to_string(),
+        '\u{1B}' => "\\e".to_string(),
+        c if (c as u32) < 0x100 => format!("\\x{:02X}", c as u32),
+        c => format!("\\u{{{:X}}}", c as u32),
+    }
+}
+
+/// Return true if the atom occupies no cell (joiners, selectors, marks, controls).
+fn is_zero_width_atom(a: &str) -> bool {
+    is_zwj(a) || is_emoji_vs(a) || is_combining_mark(a) || a.width() == 0
+}
+
+/// Return true if the unit carries zero-width atoms attached to its base.
+fn has_zero_width_atoms(unit: &str) -> bool {
+    atoms(unit).iter().any(|a| is_zero_width_atom(a))
+}
+
+/// Render one width unit as a bracketed cell, e.g. `[a]` or `[你 ]`.
+///
+/// Wide units are followed by one blank per continuation cell.
+fn render_cell(unit: &str, policy: &LayoutPolicy, color: bool) -> String {
+    let has_control = unit.chars().any(char::is_control);
+    let body: String = unit
+        .chars()
+        .map(|c| {
+            if c.is_control() {
+                escape_control(c)
+            } else {
+                c.to_string()
+            }
+        })
+        .collect();
+
+    let width = policy.display_width(unit);
+    let continuation = " ".repeat(width.saturating_sub(1));
+
+    if !color {
+        return format!("[{body}{continuation}]");
+    }
+
+    let bg = if has_control {
+        BG_CONTROL
+    } else if has_zero_width_atoms(unit) {
+        BG_ZERO_WIDTH
+    } else if width >= 2 {
+        BG_WIDE
+    } else {
+        return format!("[{body}]");
+    };
+
+    let cont = if continuation.is_empty() {
+        String::new()
+    } else {
+        paint(&continuation, BG_CONTINUATION)
+    };
+
+    format!("[{}{}]", paint(&body, bg), cont)
+}
+
+/// Render each input line as a grid of terminal cells.
+///
+/// Units come from `split_str_units(SliceMode::Width)`, so each bracket is one
+/// width-bearing cluster together with any zero-width atoms attached to it.
+/// In verbose mode, each unit is also listed with its column span and atoms.
+///
+/// # Arguments
+/// * `ctx` - Global application context.
+/// * `out` - Output writer.
+/// * `input` - Text to visualize.
+/// * `policy` - Width policy used to size cells.
+/// * `verbose` - Enable verbose output (title, per-unit details, legend).
+pub fn run_show(
+    ctx: &Context,
+    out: &mut dyn Write,
+    input: &str,
+    policy: &LayoutPolicy,
+    verbose: bool,
+) -> Result<()> {
+    // Optional header (only in verbose mode)
+    if verbose {
+        print_title(out, &ctx.t(TitleKey::CellGrid))?;
+    }
+
+    let color = use_color();
+
+    for line in input.lines() {
+        let (units, boundaries) = split_str_units(line, SliceMode::Width, policy);
+
+        let row: String = units
+            .iter()
+            .map(|u| render_cell(u, policy, color))
+            .collect();
+        writeln!(out, "{row}")?;
+
+        if !verbose {
+            continue;
+        }
+
+        // Per-unit details: index, column span and atom hints
+        for (i, unit) in units.iter().enumerate() {
+            let hints = atoms(unit)
+                .iter()
+                .map(|a| {
+                    if is_zero_width_atom(a) {
+                        format!("+{}", display_unicode_hint(a))
+                    } else {
+                        display_unicode_hint(a)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(
+                out,
+                " {:02}  {:>3}..{:<3}  {}",
+                i,
+                boundaries[i],
+                boundaries[i + 1],
+                hints
+            )?;
+        }
+        writeln!(out)?;
+    }
+
+    // Legend (only in verbose mode)
+    if verbose {
+        writeln!(out, "{}:", bold(&ctx.t(FooterKey::LegendGlossary)))?;
+        writeln!(out, "[x] = One terminal cell")?;
+        writeln!(out, "[x ] = Wide cluster (blank = continuation cell)")?;
+        writeln!(out, "+U+XXXX = Zero-width atom attached to its base")?;
+        writeln!(out, "\\t, \\e, \\xNN = Control character shown as escape")?;
+    }
+
+    Ok(())
+}
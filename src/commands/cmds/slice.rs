@@ -3,12 +3,17 @@
 //! Supports Python-style `[start:end]` expressions, with optional strict mode validation.
 //! Also provides display-aware slicing (width mode) using visual boundaries for terminal layout.
 
+use crate::commands::output::{OutputFormat, code_points, print_report};
+use crate::commands::policy::LayoutPolicy;
 use crate::config::Context;
+use crate::error::RunefixError;
 use crate::i18n::format_i18n;
 use crate::i18n::keys::{ErrorKey, FooterKey, TitleKey};
 use crate::style::print::*;
-use anyhow::{Context as _, Result, anyhow, bail};
-use runefix_core::RuneDisplayWidth;
+use crate::style::theme::Role;
+use anyhow::{Result, anyhow, bail};
+use serde_json::{Value, json};
+use std::io::{self, Write};
 use unicode_segmentation::UnicodeSegmentation;
 
 /// Slice mode enum used to determine unit of slicing.
@@ -24,32 +29,72 @@ pub enum SliceMode {
     Width,
 }
 
+impl SliceMode {
+    /// Returns the mode name used in structured output.
+    fn name(self) -> &'static str {
+        match self {
+            SliceMode::Char => "char",
+            SliceMode::Grapheme => "grapheme",
+            SliceMode::Width => "width",
+        }
+    }
+}
+
 /// Entry point for `slice` command.
 /// Handles multi-line input, slicing each line individually.
+#[allow(clippy::too_many_arguments)]
 pub fn run_slice(
     ctx: &Context,
+    out: &mut dyn Write,
     input: &str,
     slice_expr: &str,
     mode: SliceMode,
+    policy: &LayoutPolicy,
     verbose: bool,
     strict: bool,
+    format: OutputFormat,
 ) -> Result<()> {
     let lines: Vec<&str> = input.lines().collect();
     let is_single_line = lines.len() == 1;
 
+    // Structured output: one object per line (errors included)
+    if format.is_structured() {
+        let mut items = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            // Skip empty lines, as the text output does
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match slice_line(ctx, line, slice_expr, mode, policy, strict) {
+                Ok(outcome) => items.push(outcome.to_json(i, line)),
+                Err(e) if is_single_line => return Err(e),
+                Err(e) => items.push(json!({ "line": i, "text": line, "error": e.to_string() })),
+            }
+        }
+        let fields = json!({
+            "command": "slice",
+            "expr": slice_expr,
+            "mode": mode.name(),
+            "policy": policy.name,
+        });
+        print_report(out, format, fields, Some(("lines", items)))?;
+        return Ok(());
+    }
+
     for (i, line) in input.lines().enumerate() {
         // Skip or pass through empty lines
         if line.trim().is_empty() {
-            println!();
+            writeln!(out)?;
             continue;
         }
 
         // Process each line with fallback on error
-        match run_slice_single(ctx, line, slice_expr, mode, verbose, strict) {
+        match run_slice_single(ctx, out, line, slice_expr, mode, policy, verbose, strict) {
             Ok(()) => (),
             Err(e) => {
-                if is_single_line {
-                    // In single-line mode, propagate the error directly
+                if is_single_line || e.is::<io::Error>() {
+                    // In single-line mode (or on write errors), propagate the error directly
                     return Err(e);
                 } else {
                     // In multi-line mode, print error with line number
@@ -62,92 +107,163 @@ pub fn run_slice(
     Ok(())
 }
 
+/// Outcome of slicing one line.
+struct SliceOutcome {
+    /// The sliced text
+    result: String,
+
+    /// Total units (or columns, in width mode) in the line
+    total: usize,
+
+    /// Requested bounds (clamped in char/grapheme mode)
+    start: usize,
+    end: usize,
+
+    /// Byte offset of the result within the line
+    byte_start: usize,
+
+    /// Whether the requested range runs past the end of the line
+    out_of_range: bool,
+}
+
+impl SliceOutcome {
+    /// Describes the outcome of line `index` for structured output.
+    fn to_json(&self, index: usize, line: &str) -> Value {
+        let char_start = line[..self.byte_start].chars().count();
+        json!({
+            "line": index,
+            "text": line,
+            "result": self.result,
+            "total": self.total,
+            "start": self.start,
+            "end": self.end,
+            "byte_start": self.byte_start,
+            "byte_end": self.byte_start + self.result.len(),
+            "char_start": char_start,
+            "char_end": char_start + self.result.chars().count(),
+            "code_points": code_points(&self.result),
+        })
+    }
+}
+
 /// Core slice handler: supports Python-style slicing with display-width logic.
+#[allow(clippy::too_many_arguments)]
 pub fn run_slice_single(
     ctx: &Context,
+    out: &mut dyn Write,
     input: &str,
     slice_expr: &str,
     mode: SliceMode,
+    policy: &LayoutPolicy,
     verbose: bool,
     strict: bool,
 ) -> Result<()> {
     // Optional header (only in verbose mode)
     if verbose {
         // Note: stdout/stderr output order may vary due to OS-level buffering.
-        print_title(&ctx.t(TitleKey::SlicePreview));
+        print_title(out, &ctx.t(TitleKey::SlicePreview))?;
+    }
+
+    let outcome = slice_line(ctx, input, slice_expr, mode, policy, strict)?;
+
+    // Output result
+    writeln!(out, "{}", outcome.result)?;
+
+    // Optionally print summary footer
+    maybe_print_footer(ctx, out, verbose, outcome.total, outcome.start, outcome.end)?;
+
+    // Mark ranges that were clamped to the end of the line
+    if verbose && outcome.out_of_range {
+        let msg = format_i18n(
+            &ctx.t(FooterKey::SliceOutOfRange),
+            &[
+                ("expr", slice_expr.to_string()),
+                ("total", outcome.total.to_string()),
+            ],
+        );
+        writeln!(out, "{}", ctx.theme.paint(Role::OutOfRange, &msg))?;
     }
 
+    Ok(())
+}
+
+/// Slices a single line, validating bounds in strict mode.
+fn slice_line(
+    ctx: &Context,
+    input: &str,
+    slice_expr: &str,
+    mode: SliceMode,
+    policy: &LayoutPolicy,
+    strict: bool,
+) -> Result<SliceOutcome> {
     // Split string into units based on slice mode
-    let (units, visual_boundaries) = split_str_units(input, mode);
+    let (units, visual_boundaries) = split_str_units(input, mode, policy);
 
     // Parse user input: slice expression like [2:5]
-    let range = parse_slice_range(ctx, slice_expr).context(ctx.t(ErrorKey::SliceExprFallback))?;
+    let range = parse_slice_range(ctx, slice_expr)?;
 
     let start = range.start;
     let end = range.end.unwrap_or(units.len());
+    let byte_offset = |i: usize| units[..i].iter().map(|u| u.len()).sum();
 
     // Case 1: char/grapheme slicing
     if !matches!(mode, SliceMode::Width) {
         // Strict mode: check for out-of-bounds or reversed range
         if strict && (start > units.len() || end > units.len() || start > end) {
-            let msg = format_i18n(
-                &ctx.t(ErrorKey::SliceOutOfBounds),
+            bail!(RunefixError::with_args(
+                ctx,
+                ErrorKey::SliceOutOfBounds,
                 &[("len", units.len().to_string())],
-            );
-            bail!(msg);
+            ));
         }
 
-        // Non-strict: clamp range to safe bounds
+        // Non-strict: clamp range to safe bounds (a reversed range is empty)
         let safe_start = start.min(units.len());
-        let safe_end = end.min(units.len());
-
-        // Output result
-        let sliced = &units[safe_start..safe_end];
-        println!("{}", sliced.concat());
-
-        // Optionally print summary footer
-        maybe_print_footer(ctx, verbose, units.len(), safe_start, safe_end);
-
-        return Ok(());
+        let safe_end = end.min(units.len()).max(safe_start);
+
+        return Ok(SliceOutcome {
+            result: units[safe_start..safe_end].concat(),
+            total: units.len(),
+            start: safe_start,
+            end: safe_end,
+            byte_start: byte_offset(safe_start),
+            out_of_range: start > units.len() || end > units.len(),
+        });
     }
 
-    // Case 2: display-width slicing
-    if matches!(mode, SliceMode::Width) {
-        // Strict: start/end must be aligned to display boundaries
-        if strict && (!visual_boundaries.contains(&start) || !visual_boundaries.contains(&end)) {
-            let msg = format_i18n(
-                &ctx.t(ErrorKey::SliceWidthUnaligned),
-                &[("boundaries", format!("{:?}", visual_boundaries))],
-            );
-            bail!(msg);
-        }
+    // Case 2: display-width slicing (an open end runs to the last column)
+    let total = visual_boundaries.last().copied().unwrap_or(0);
+    let end = range.end.unwrap_or(total);
 
-        // Non-strict: fallback to nearest boundary
-        let slice_start = visual_boundaries
-            .iter()
-            .position(|&v| v >= start)
-            .unwrap_or(visual_boundaries.len() - 1);
-        let slice_end = visual_boundaries
-            .iter()
-            .position(|&v| v >= end)
-            .unwrap_or(visual_boundaries.len());
-
-        let sliced = &units[slice_start..slice_end];
-        println!("{}", sliced.concat());
-
-        // Optional summary
-        maybe_print_footer(
+    // Strict: start/end must be aligned to display boundaries
+    if strict && (!visual_boundaries.contains(&start) || !visual_boundaries.contains(&end)) {
+        bail!(RunefixError::with_args(
             ctx,
-            verbose,
-            visual_boundaries.last().copied().unwrap_or(0),
-            start,
-            end,
-        );
-
-        return Ok(());
+            ErrorKey::SliceWidthUnaligned,
+            &[("boundaries", format!("{:?}", visual_boundaries))],
+        ));
     }
 
-    Ok(())
+    // Non-strict: fallback to nearest boundary, clamped to the line
+    let slice_start = visual_boundaries
+        .iter()
+        .position(|&v| v >= start)
+        .unwrap_or(units.len())
+        .min(units.len());
+    let slice_end = visual_boundaries
+        .iter()
+        .position(|&v| v >= end)
+        .unwrap_or(units.len())
+        .clamp(slice_start, units.len());
+
+    Ok(SliceOutcome {
+        result: units[slice_start..slice_end].concat(),
+        total,
+        start,
+        end,
+        byte_start: byte_offset(slice_start),
+        out_of_range: start > total || end > total,
+    })
 }
 
 /// Result of parsed slice expression, e.g. [start:end]
@@ -211,12 +327,12 @@ fn parse_slice_range(ctx: &Context, expr: &str) -> Result<ParsedRange> {
 
     // Leading/trailing spaces are not allowed
     if expr != expr.trim() {
-        bail!(ctx.t(ErrorKey::SliceTrim));
+        bail!(RunefixError::new(ctx, ErrorKey::SliceTrim));
     }
 
     // Must start with `[` and end with `]`
     if !expr.starts_with('[') || !expr.ends_with(']') {
-        bail!(ctx.t(ErrorKey::SliceBrackets));
+        bail!(RunefixError::new(ctx, ErrorKey::SliceBrackets));
     }
 
     // Extract the inner content, e.g. "[1:3]" → "1:3"
@@ -224,14 +340,14 @@ fn parse_slice_range(ctx: &Context, expr: &str) -> Result<ParsedRange> {
 
     // Inner spaces are not allowed (e.g. `[ 1 : 3 ]` is invalid)
     if content.contains(char::is_whitespace) {
-        bail!(ctx.t(ErrorKey::SliceSpaces));
+        bail!(RunefixError::new(ctx, ErrorKey::SliceSpaces));
     }
 
     // Handle shorthand `[N]` → interpreted as `[N:N+1]`
     if !content.contains(':') {
         let idx = content
             .parse::<usize>()
-            .map_err(|_| anyhow!(ctx.t(ErrorKey::SliceParseSingle)))?;
+            .map_err(|_| anyhow!(RunefixError::new(ctx, ErrorKey::SliceParseSingle)))?;
 
         return Ok(ParsedRange {
             start: idx,
@@ -242,7 +358,7 @@ fn parse_slice_range(ctx: &Context, expr: &str) -> Result<ParsedRange> {
     // Handle full `[start:end]` form
     let parts: Vec<_> = content.split(':').collect();
     if parts.len() != 2 {
-        bail!(ctx.t(ErrorKey::SliceFormat));
+        bail!(RunefixError::new(ctx, ErrorKey::SliceFormat));
     }
 
     // Parse the `start` part
@@ -251,7 +367,7 @@ fn parse_slice_range(ctx: &Context, expr: &str) -> Result<ParsedRange> {
     } else {
         parts[0]
             .parse::<usize>()
-            .map_err(|_| anyhow!(ctx.t(ErrorKey::SliceParseStart)))?
+            .map_err(|_| anyhow!(RunefixError::new(ctx, ErrorKey::SliceParseStart)))?
     };
 
     // Parse the `end` part
@@ -261,7 +377,7 @@ fn parse_slice_range(ctx: &Context, expr: &str) -> Result<ParsedRange> {
         Some(
             parts[1]
                 .parse::<usize>()
-                .map_err(|_| anyhow!(ctx.t(ErrorKey::SliceParseEnd)))?,
+                .map_err(|_| anyhow!(RunefixError::new(ctx, ErrorKey::SliceParseEnd)))?,
         )
     };
 
@@ -271,8 +387,13 @@ fn parse_slice_range(ctx: &Context, expr: &str) -> Result<ParsedRange> {
 
 /// Splits input string into units by mode (char / grapheme / width).
 ///
-/// Returns both the unit slices and their boundary indices.
-pub fn split_str_units(input: &str, mode: SliceMode) -> (Vec<&str>, Vec<usize>) {
+/// Returns both the unit slices and their boundary indices. Width mode measures
+/// graphemes with `policy`.
+pub fn split_str_units<'a>(
+    input: &'a str,
+    mode: SliceMode,
+    policy: &LayoutPolicy,
+) -> (Vec<&'a str>, Vec<usize>) {
     match mode {
         // Char mode: split by individual Unicode scalar values
         SliceMode::Char => {
@@ -301,7 +422,7 @@ pub fn split_str_units(input: &str, mode: SliceMode) -> (Vec<&str>, Vec<usize>)
             let mut width_total = 0; // Running total of column width
 
             for g in input.graphemes(true) {
-                let w = g.display_width(); // Use runefix-core for precise width
+                let w = policy.width(g); // Policy-aware width (ambiguous, overrides)
 
                 acc.push_str(g);
                 acc_width += w;
@@ -331,7 +452,14 @@ pub fn split_str_units(input: &str, mode: SliceMode) -> (Vec<&str>, Vec<usize>)
 }
 
 /// Optionally prints a footer with slice summary (verbose mode only).
-fn maybe_print_footer(ctx: &Context, verbose: bool, total: usize, start: usize, end: usize) {
+fn maybe_print_footer(
+    ctx: &Context,
+    out: &mut dyn Write,
+    verbose: bool,
+    total: usize,
+    start: usize,
+    end: usize,
+) -> io::Result<()> {
     if verbose {
         let msg = format_i18n(
             &ctx.t(FooterKey::SliceSummary),
@@ -341,6 +469,7 @@ fn maybe_print_footer(ctx: &Context, verbose: bool, total: usize, start: usize,
                 ("end", end.to_string()),
             ],
         );
-        println!("\n{msg}");
+        writeln!(out, "\n{msg}")?;
     }
+    Ok(())
 }
@@ -1,9 +1,14 @@
 //! Execute the `split` command: split text into lines based on display width.
 
+use crate::commands::output::{OutputFormat, print_report, segments};
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::tabs::{TabStops, display_width_with_tabs, split_with_tabs};
 use crate::config::Context;
 use crate::i18n::keys::{ReportKey, TitleKey};
 use crate::style::print::*;
-use runefix_core::{RuneDisplayWidth, WidthPolicy, split_by_width_with_policy};
+use anyhow::Result;
+use serde_json::json;
+use std::io::Write;
 
 /// Split input text into lines by cumulative display width and print each line.
 ///
@@ -13,35 +18,73 @@ use runefix_core::{RuneDisplayWidth, WidthPolicy, split_by_width_with_policy};
 ///
 /// # Arguments
 /// * `ctx` - Global application context (for i18n and theming).
+/// * `out` - Output writer.
 /// * `text` - Input string to split.
 /// * `max_width` - Maximum allowed display width per line.
 /// * `policy` - Display width strategy (e.g., terminal, markdown).
+/// * `tabs` - Optional tab stops; tabs are measured up to the next stop.
 /// * `verbose` - Enable verbose output.
-pub fn run_split(ctx: &Context, text: &str, max_width: usize, policy: WidthPolicy, verbose: bool) {
+/// * `format` - Output format (`text`, `json`, `ndjson`).
+#[allow(clippy::too_many_arguments)]
+pub fn run_split(
+    ctx: &Context,
+    out: &mut dyn Write,
+    text: &str,
+    max_width: usize,
+    policy: LayoutPolicy,
+    tabs: Option<&TabStops>,
+    verbose: bool,
+    format: OutputFormat,
+) -> Result<()> {
     // Optional header (only in verbose mode)
-    if verbose {
+    if verbose && !format.is_structured() {
         // Display localized section title
-        print_title(&ctx.t(TitleKey::SplitLines));
+        print_title(out, &ctx.t(TitleKey::SplitLines))?;
     }
 
     // Perform width-aware splitting
-    let lines = split_by_width_with_policy(text, max_width, Some(&policy));
+    let lines = match tabs {
+        Some(stops) => split_with_tabs(text, max_width, stops, &policy),
+        None => policy.split_by_width(text, max_width),
+    };
+
+    // Measure a line, honoring tab stops if given
+    let measure = |line: &str| match tabs {
+        Some(stops) => display_width_with_tabs(line, stops, &policy),
+        None => policy.display_width(line),
+    };
+
+    // Structured output: one object per line
+    if format.is_structured() {
+        let items = segments(text, lines.iter().map(|l| (l.as_str(), measure(l))));
+        let fields = json!({
+            "command": "split",
+            "text": text,
+            "policy": policy.name,
+            "max_width": max_width,
+        });
+        print_report(out, format, fields, Some(("lines", items)))?;
+        return Ok(());
+    }
 
     // Compute max line width for alignment
-    let max_display_width = lines.iter().map(|line| line.width()).max().unwrap_or(0);
+    let max_display_width = lines.iter().map(|line| measure(line)).max().unwrap_or(0);
 
     // Print each line with padding and width info
     for (i, line) in lines.iter().enumerate() {
-        let width = line.width();
+        let width = measure(line);
         let pad = max_display_width - width;
 
-        println!(
+        writeln!(
+            out,
             "{}{:>2}:    [{}{}] (width = {})",
             ctx.t(ReportKey::SplitLine),
             i + 1,
             line,
             " ".repeat(pad),
             width
-        );
+        )?;
     }
+
+    Ok(())
 }
@@ -0,0 +1,93 @@
+//! Execute the `testpattern` command: print alignment grids of tricky graphemes,
+//! each closed by a `|` where runefix expects it, so terminal disagreements
+//! show up as ragged columns.
+
+use crate::commands::output::code_points;
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::samples::{SAMPLES, SampleCategory};
+use crate::config::Context;
+use crate::i18n::keys::{FooterKey, TitleKey};
+use crate::style::frame::{Align, align_text};
+use crate::style::{print::*, term::*};
+use anyhow::Result;
+use std::io::Write;
+
+/// How many times each sample is repeated on its row.
+const REPEAT: usize = 4;
+
+/// Column (0-based) at which the closing `|` is expected.
+const GUIDE_COLUMN: usize = 16;
+
+/// Print a test pattern for the selected sample categories.
+///
+/// Each category starts with an ASCII reference row; every sample row repeats
+/// the grapheme and pads it (by policy width) so its `|` should line up with
+/// the reference. Misaligned bars reveal where the terminal disagrees.
+///
+/// # Arguments
+/// * `ctx` - Global application context.
+/// * `out` - Output writer.
+/// * `categories` - Categories to include (all when empty).
+/// * `policy` - Width policy used to place the guide bars.
+/// * `verbose` - Enable verbose output (title, expected widths, legend).
+pub fn run_testpattern(
+    ctx: &Context,
+    out: &mut dyn Write,
+    categories: &[SampleCategory],
+    policy: LayoutPolicy,
+    verbose: bool,
+) -> Result<()> {
+    // Optional header (only in verbose mode)
+    if verbose {
+        print_title(out, &ctx.t(TitleKey::TestPattern))?;
+    }
+
+    let reference = format!("{}|", ".".repeat(GUIDE_COLUMN));
+    let mut current = None;
+
+    for sample in SAMPLES {
+        if !categories.is_empty() && !categories.contains(&sample.category) {
+            continue;
+        }
+
+        // Category heading and reference row
+        if current != Some(sample.category) {
+            if current.is_some() {
+                writeln!(out)?;
+            }
+            current = Some(sample.category);
+
+            writeln!(out, "{}", bold(sample.category.label()))?;
+            writeln!(out, "{reference}")?;
+        }
+
+        let text = sample.text.repeat(REPEAT);
+        let padded = align_text(&text, GUIDE_COLUMN, Align::Left, &policy);
+        let mut row = format!("{padded}|  {}", code_points(sample.text).join(" "));
+        if verbose {
+            let w = policy.display_width(sample.text);
+            row.push_str(&format!("  (width {w})"));
+        }
+        writeln!(out, "{row}")?;
+    }
+
+    // Legend (only in verbose mode)
+    if verbose {
+        writeln!(out)?;
+        writeln!(out, "{}:", bold(&ctx.t(FooterKey::LegendGlossary)))?;
+        writeln!(
+            out,
+            "{reference} = Reference column ({GUIDE_COLUMN} ASCII cells)"
+        )?;
+        writeln!(
+            out,
+            "Bar left of reference = Terminal renders the grapheme narrower than expected"
+        )?;
+        writeln!(
+            out,
+            "Bar right of reference = Terminal renders the grapheme wider than expected"
+        )?;
+    }
+
+    Ok(())
+}
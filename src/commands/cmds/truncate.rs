@@ -1,9 +1,14 @@
 //! Execute the `truncate` command: trim text based on display width constraint.
 
+use crate::commands::output::{OutputFormat, print_report};
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::tabs::{TabStops, display_width_with_tabs, truncate_with_tabs};
 use crate::config::Context;
 use crate::i18n::keys::TitleKey;
 use crate::style::print::*;
-use runefix_core::{WidthPolicy, truncate_by_width_with_policy};
+use anyhow::Result;
+use serde_json::json;
+use std::io::Write;
 
 /// Truncate the input string to a maximum display width using the provided policy.
 ///
@@ -11,26 +16,59 @@ use runefix_core::{WidthPolicy, truncate_by_width_with_policy};
 ///
 /// # Arguments
 /// * `ctx` - Global application context (for i18n, etc.).
+/// * `out` - Output writer.
 /// * `input` - Text to be truncated.
 /// * `max_width` - Maximum allowed display width in columns.
 /// * `policy` - Display width strategy (e.g., terminal-aware, markdown-safe).
+/// * `tabs` - Optional tab stops; tabs are measured up to the next stop.
 /// * `verbose` - Enable verbose output.
+/// * `format` - Output format (`text`, `json`, `ndjson`).
+#[allow(clippy::too_many_arguments)]
 pub fn run_truncate(
     ctx: &Context,
+    out: &mut dyn Write,
     input: &str,
     max_width: usize,
-    policy: WidthPolicy,
+    policy: LayoutPolicy,
+    tabs: Option<&TabStops>,
     verbose: bool,
-) {
+    format: OutputFormat,
+) -> Result<()> {
     // Optional header (only in verbose mode)
-    if verbose {
+    if verbose && !format.is_structured() {
         // Display localized section title
-        print_title(&ctx.t(TitleKey::TruncatedOutput));
+        print_title(out, &ctx.t(TitleKey::TruncatedOutput))?;
     }
 
     // Apply truncation based on display width
-    let result = truncate_by_width_with_policy(input, max_width, Some(&policy));
+    let result = match tabs {
+        Some(stops) => truncate_with_tabs(input, max_width, stops, &policy),
+        None => policy.truncate_by_width(input, max_width),
+    };
+
+    // Structured output: the kept prefix with its bounds
+    if format.is_structured() {
+        let width = match tabs {
+            Some(stops) => display_width_with_tabs(result, stops, &policy),
+            None => policy.display_width(result),
+        };
+        let fields = json!({
+            "command": "truncate",
+            "text": input,
+            "policy": policy.name,
+            "max_width": max_width,
+            "result": result,
+            "width": width,
+            "truncated": result.len() < input.len(),
+            "byte_end": result.len(),
+            "char_end": result.chars().count(),
+        });
+        print_report(out, format, fields, None)?;
+        return Ok(());
+    }
 
     // Print the truncated result
-    println!("{result}");
+    writeln!(out, "{result}")?;
+
+    Ok(())
 }
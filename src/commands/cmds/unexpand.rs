@@ -0,0 +1,28 @@
+//! Execute the `unexpand` command: convert blanks back to tabs using display-column tab stops.
+
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::tabs::{TabStops, unexpand_line};
+use anyhow::Result;
+use std::io::Write;
+
+/// Convert blank runs ending on a tab stop into tabs, line by line.
+///
+/// # Arguments
+/// * `out` - Output writer.
+/// * `input` - Text to unexpand (processed line by line).
+/// * `stops` - Tab stop positions.
+/// * `policy` - Display width strategy.
+/// * `all` - Convert all blank runs, not only leading ones.
+pub fn run_unexpand(
+    out: &mut dyn Write,
+    input: &str,
+    stops: &TabStops,
+    policy: LayoutPolicy,
+    all: bool,
+) -> Result<()> {
+    for line in input.lines() {
+        writeln!(out, "{}", unexpand_line(line, stops, &policy, all))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+//! Execute the `unicode-diff` command: list code points whose width changes
+//! between two Unicode versions under a policy.
+
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::unicode_age::{UnicodeVersion, age};
+use crate::config::Context;
+use crate::i18n::format_i18n;
+use crate::i18n::keys::{FooterKey, TitleKey};
+use crate::style::print::*;
+use crate::style::table::Table;
+use anyhow::Result;
+use std::io::Write;
+
+/// A run of consecutive code points with the same age and widths.
+struct Change {
+    first: char,
+    last: char,
+    age: UnicodeVersion,
+    from: usize,
+    to: usize,
+}
+
+/// Formats a code point range as `U+XXXX` or `U+XXXX..U+YYYY`.
+fn range_label(first: char, last: char) -> String {
+    if first == last {
+        format!("U+{:04X}", first as u32)
+    } else {
+        format!("U+{:04X}..U+{:04X}", first as u32, last as u32)
+    }
+}
+
+/// List code points whose width differs between two Unicode versions.
+///
+/// Only code points assigned after the older version can change; under the
+/// older version they are measured as one-column tofu. Consecutive code
+/// points with the same age and widths are grouped unless `verbose` is set.
+///
+/// # Arguments
+/// * `ctx` - Global application context.
+/// * `out` - Output writer.
+/// * `from` - Version to compare from.
+/// * `to` - Version to compare to.
+/// * `policy` - Layout policy used for both measurements.
+/// * `verbose` - Enable verbose output (title, one row per code point).
+pub fn run_unicode_diff(
+    ctx: &Context,
+    out: &mut dyn Write,
+    from: UnicodeVersion,
+    to: UnicodeVersion,
+    policy: LayoutPolicy,
+    verbose: bool,
+) -> Result<()> {
+    // Optional header (only in verbose mode)
+    if verbose {
+        print_title(out, &ctx.t(TitleKey::UnicodeDiff))?;
+    }
+
+    let with_version = |v| LayoutPolicy {
+        unicode_version: Some(v),
+        ..policy.clone()
+    };
+    let (old, new) = (with_version(from), with_version(to));
+    let (lo, hi) = (from.min(to), from.max(to));
+
+    let mut changes: Vec<Change> = Vec::new();
+    let mut count = 0;
+    for c in '\0'..=char::MAX {
+        // Unassigned code points have no age, so no version changes them
+        let Some(a) = age(c) else { continue };
+        if a <= lo || a > hi || c.is_control() {
+            continue;
+        }
+
+        let mut buf = [0; 4];
+        let s = c.encode_utf8(&mut buf);
+        let (w_from, w_to) = (old.width(s), new.width(s));
+        if w_from == w_to {
+            continue;
+        }
+
+        count += 1;
+        match changes.last_mut() {
+            Some(ch)
+                if !verbose
+                    && ch.last as u32 + 1 == c as u32
+                    && (ch.age, ch.from, ch.to) == (a, w_from, w_to) =>
+            {
+                ch.last = c;
+            }
+            _ => changes.push(Change {
+                first: c,
+                last: c,
+                age: a,
+                from: w_from,
+                to: w_to,
+            }),
+        }
+    }
+
+    let mut table = Table::new([
+        "Code Points".to_string(),
+        "Count".to_string(),
+        "Age".to_string(),
+        format!("{from} → {to}"),
+        "Sample".to_string(),
+    ]);
+
+    // Rows
+    for ch in &changes {
+        let count = ch.last as u32 - ch.first as u32 + 1;
+        table.row(vec![
+            range_label(ch.first, ch.last),
+            count.to_string(),
+            ch.age.to_string(),
+            format!("{} → {}", ch.from, ch.to),
+            ch.first.to_string(),
+        ]);
+    }
+
+    // Summary
+    table.divider();
+    table.print(out, &ctx.theme)?;
+    let summary = format_i18n(
+        &ctx.t(FooterKey::UnicodeDiffSummary),
+        &[
+            ("count", count.to_string()),
+            ("ranges", changes.len().to_string()),
+            ("from", from.to_string()),
+            ("to", to.to_string()),
+        ],
+    );
+    writeln!(out, " {summary}")?;
+
+    Ok(())
+}
@@ -1,9 +1,13 @@
 //! Execute the `version` command: show CLI, Core, and Unicode version info.
 
+use crate::commands::output::{OutputFormat, print_report};
 use crate::config::Context;
 use crate::i18n::keys::TitleKey;
 use crate::style::print::*;
+use anyhow::Result;
 use runefix_core::UNICODE_VERSION;
+use serde_json::json;
+use std::io::Write;
 
 /// Runefix CLI version (from Cargo.toml)
 const CLI_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -45,22 +49,38 @@ fn extract_core_version_from_lock() -> Option<String> {
 ///
 /// # Arguments
 /// * `ctx` - Global application context.
+/// * `out` - Output writer.
 /// * `json` - Whether to print output as pretty JSON.
-pub fn run_version(ctx: &Context, json: bool) {
+/// * `format` - Output format (`--json` is shorthand for `json`).
+pub fn run_version(
+    ctx: &Context,
+    out: &mut dyn Write,
+    json: bool,
+    format: OutputFormat,
+) -> Result<()> {
     let core_version = extract_core_version_from_lock().unwrap_or_else(|| "<unknown>".to_string());
     let (major, minor, patch) = UNICODE_VERSION;
     let unicode_version = format!("{}.{}.{}", major, minor, patch);
 
-    if json {
-        println!(
-            "{{\n  \"cli-version\": \"{}\",\n  \"core-version\": \"{}\",\n  \"unicode-version\": \"{}\"\n}}",
-            CLI_VERSION, core_version, unicode_version
-        );
+    if json || format.is_structured() {
+        let format = if json && !format.is_structured() {
+            OutputFormat::Json
+        } else {
+            format
+        };
+        let fields = json!({
+            "cli-version": CLI_VERSION,
+            "core-version": core_version,
+            "unicode-version": unicode_version,
+        });
+        print_report(out, format, fields, None)?;
     } else {
-        print_title(&ctx.t(TitleKey::RunefixVersion));
+        print_title(out, &ctx.t(TitleKey::RunefixVersion))?;
 
-        println!("{:<16}{}", "Runefix CLI", CLI_VERSION);
-        println!("{:<16}{}", "Runefix Core", core_version);
-        println!("{:<16}{}", "Unicode Data", unicode_version);
+        writeln!(out, "{:<16}{}", "Runefix CLI", CLI_VERSION)?;
+        writeln!(out, "{:<16}{}", "Runefix Core", core_version)?;
+        writeln!(out, "{:<16}{}", "Unicode Data", unicode_version)?;
     }
+
+    Ok(())
 }
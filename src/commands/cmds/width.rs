@@ -1,70 +1,94 @@
 //! Execute the `width` command: compute total display width of input text.
 
+use crate::commands::output::{OutputFormat, print_report};
+use crate::commands::policy::{LayoutPolicy, PROFILES};
+use crate::commands::tabs::{TabStops, display_width_with_tabs};
 use crate::config::Context;
 use crate::i18n::format_i18n;
 use crate::i18n::keys::{ReportKey, TitleKey};
 use crate::style::print::*;
-use runefix_core::WidthPolicy;
+use anyhow::Result;
+use serde_json::json;
+use std::io::Write;
 
 /// Compute and print the display width of the entire input string.
 ///
-/// Uses the specified `WidthPolicy` to determine how wide the string renders
+/// Uses the specified `LayoutPolicy` to determine how wide the string renders
 /// in terminal or other contexts.
 ///
 /// # Arguments
 /// * `ctx` - Global application context.
+/// * `out` - Output writer.
 /// * `input` - The text to analyze.
 /// * `policy` - Display width strategy.
+/// * `tabs` - Optional tab stops; tabs are measured up to the next stop.
 /// * `verbose` - Enable verbose output.
-pub fn run_width(ctx: &Context, input: &str, policy: WidthPolicy, verbose: bool) {
+/// * `format` - Output format (`text`, `json`, `ndjson`).
+pub fn run_width(
+    ctx: &Context,
+    out: &mut dyn Write,
+    input: &str,
+    policy: LayoutPolicy,
+    tabs: Option<&TabStops>,
+    verbose: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    // Calculate display width using selected policy
+    let width = match tabs {
+        Some(stops) => display_width_with_tabs(input, stops, &policy),
+        None => policy.display_width(input),
+    };
+
+    // Structured output: a single object
+    if format.is_structured() {
+        let fields = json!({
+            "command": "width",
+            "text": input,
+            "policy": policy.name,
+            "width": width,
+        });
+        print_report(out, format, fields, None)?;
+        return Ok(());
+    }
+
     // Optional header (only in verbose mode)
     if verbose {
         // Display localized section title
-        print_title(&ctx.t(TitleKey::DisplayWidth));
+        print_title(out, &ctx.t(TitleKey::DisplayWidth))?;
     }
 
-    // Calculate display width using selected policy
-    let width = runefix_core::display_width_with_policy(input, Some(&policy));
-
     // Print input and display width information
-    println!("{}: \"{}\"", ctx.t(ReportKey::WidthText), input);
-    println!(
+    writeln!(out, "{}: \"{}\"", ctx.t(ReportKey::WidthText), input)?;
+    writeln!(
+        out,
         "{}: {}",
         ctx.t(ReportKey::WidthPolicy),
         describe_policy(&policy)
-    );
-    println!();
+    )?;
+    writeln!(out)?;
     let msg = format_i18n(
         &ctx.t(ReportKey::WidthDetail),
         &[("total", width.to_string())],
     );
-    println!("{}", msg);
+    writeln!(out, "{}", msg)?;
+
+    Ok(())
 }
 
 /// Returns the name of a given width policy (for CLI display or logging).
 ///
-/// Matches the policy against built-in presets and returns:
+/// Returns the display name of a built-in preset or emulator profile:
 /// - `"Terminal"`
 /// - `"Markdown"`
 /// - `"Compact"`
-/// - `"Custom"` (for unknown or user-defined configurations)
-fn describe_policy(policy: &WidthPolicy) -> &'static str {
-    // Match by comparing fields using known constructor output
-    if policy_eq(policy, &WidthPolicy::terminal()) {
-        "Terminal"
-    } else if policy_eq(policy, &WidthPolicy::markdown()) {
-        "Markdown"
-    } else if policy_eq(policy, &WidthPolicy::compact()) {
-        "Compact"
-    } else {
-        "Custom"
+/// - the profile name (e.g. `"kitty"`)
+/// - `"<name> (custom)"` for policies defined in the config file
+fn describe_policy(policy: &LayoutPolicy) -> String {
+    match policy.name.as_str() {
+        "terminal" => "Terminal".to_string(),
+        "markdown" => "Markdown".to_string(),
+        "compact" => "Compact".to_string(),
+        name if PROFILES.contains(&name) => name.to_string(),
+        name => format!("{name} (custom)"),
     }
 }
-
-/// Compares two policies by their underlying field values.
-///
-/// This is used instead of `PartialEq` to avoid trait bounds.
-/// Policies are compared as tuples of internal fields.
-fn policy_eq(a: &WidthPolicy, b: &WidthPolicy) -> bool {
-    a.as_tuple() == b.as_tuple()
-}
@@ -1,34 +1,79 @@
 //! Execute the `widths` command: compute width of each grapheme cluster using a specified policy.
 
+use super::atoms::segment_role;
+use crate::commands::output::{OutputFormat, print_report, print_segment_table, segments};
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::tabs::{TabStops, grapheme_widths_with_tabs};
 use crate::config::Context;
 use crate::i18n::keys::TitleKey;
 use crate::style::print::*;
-use runefix_core::{WidthPolicy, display_width_with_policy};
-use unicode_segmentation::UnicodeSegmentation;
+use anyhow::Result;
+use serde_json::json;
+use std::io::Write;
 
 /// Compute and print the display width of each grapheme in the input string.
 ///
-/// This function uses `UnicodeSegmentation` to divide the input into grapheme clusters,
-/// then calculates the display width of each using the provided `WidthPolicy`.
+/// This function divides the input into grapheme clusters, then calculates
+/// the display width of each using the provided `LayoutPolicy`.
 ///
 /// # Arguments
 /// * `ctx` - Global application context (used for i18n, theme, etc.).
+/// * `out` - Output writer.
 /// * `input` - Raw input string to analyze.
 /// * `policy` - Display width strategy (e.g., terminal, markdown).
+/// * `tabs` - Optional tab stops; each tab is as wide as the gap to the next stop.
 /// * `verbose` - Enable verbose output.
-pub fn run_widths(ctx: &Context, input: &str, policy: WidthPolicy, verbose: bool) {
+/// * `format` - Output format (`text`, `json`, `ndjson`, `csv`, `tsv`).
+pub fn run_widths(
+    ctx: &Context,
+    out: &mut dyn Write,
+    input: &str,
+    policy: LayoutPolicy,
+    tabs: Option<&TabStops>,
+    verbose: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    // Split input into Unicode grapheme clusters with their widths
+    let graphemes = match tabs {
+        Some(stops) => grapheme_widths_with_tabs(input, stops, &policy),
+        None => policy.grapheme_widths(input),
+    };
+
+    // Structured output: one object per grapheme
+    if format.is_structured() {
+        let total: usize = graphemes.iter().map(|(_, w)| w).sum();
+        let fields = json!({
+            "command": "widths",
+            "text": input,
+            "policy": policy.name,
+            "width": total,
+        });
+        let items = segments(input, graphemes);
+        print_report(out, format, fields, Some(("graphemes", items)))?;
+        return Ok(());
+    }
+
+    // Tabular output: one row per grapheme
+    if format.is_tabular() {
+        print_segment_table(out, format, graphemes)?;
+        return Ok(());
+    }
+
     // Optional header (only in verbose mode)
     if verbose {
         // Display localized section title
-        print_title(&ctx.t(TitleKey::WidthPerChar));
+        print_title(out, &ctx.t(TitleKey::WidthPerChar))?;
     }
 
-    // Split input into Unicode grapheme clusters
-    let graphemes = input.graphemes(true).collect::<Vec<&str>>();
-
     // Iterate through each grapheme and print its display width
-    for g in graphemes {
-        let width = display_width_with_policy(g, Some(&policy));
-        println!("[{}] = {}", g, width);
+    for (g, width) in graphemes {
+        let line = format!("[{}] = {}", g, width);
+        writeln!(
+            out,
+            "{}",
+            ctx.theme.paint_opt(segment_role(g, width), &line)
+        )?;
     }
+
+    Ok(())
 }
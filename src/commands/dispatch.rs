@@ -4,26 +4,52 @@
 //! This module connects `clap`-derived argument structures to actual `run_*` functions.
 
 use crate::commands::cli::{
-    Cli, Commands, PolicyInput, SliceArgs, TextInput, VersionArgs, WidthInput,
+    BoxArgs, Cli, Commands, CompareArgs, ExpandArgs, ExportTableArgs, FlattenArgs, PolicyArgs,
+    PolicyCommand, PolicyDiffArgs, PolicyInput, ProbeArgs, RulerArgs, ScreenArgs, ShowArgs,
+    SliceArgs, TestpatternArgs, TextInput, UnexpandArgs, UnicodeDiffArgs, VersionArgs, WidthInput,
 };
+use crate::commands::cmds::export_table::TableFormat;
 use crate::commands::cmds::slice::SliceMode;
 use crate::commands::cmds::*;
-use crate::commands::input::resolve_input;
+use crate::commands::input::{resolve_input, resolve_optional_input};
+use crate::commands::output::OutputFormat;
+use crate::commands::overrides::load_overrides;
+use crate::commands::overstrike::flatten_text;
+use crate::commands::policy::{AmbiguousWidth, LayoutPolicy, is_known_policy, parse_policy};
+use crate::commands::tabs::{TabStops, parse_tab_stops};
 use crate::config::Context;
-use anyhow::Result;
-use runefix_core::WidthPolicy;
-
-/// Parses a policy name string into a `WidthPolicy` object.
-/// Falls back to `terminal` policy if the name is unknown.
-fn parse_policy(name: &str) -> WidthPolicy {
-    match name {
-        "terminal" => WidthPolicy::terminal(),
-        "markdown" => WidthPolicy::markdown(),
-        "compact" => WidthPolicy::compact(),
-        _ => {
-            eprintln!("⚠ Unknown policy '{name}', falling back to terminal.");
-            WidthPolicy::terminal()
-        }
+use crate::error::RunefixError;
+use crate::i18n::keys::ErrorKey;
+use crate::style::term::{WidthSpec, is_stdout_terminal};
+use anyhow::{Result, bail};
+use std::io::Write;
+
+/// Resolves input text, replaying `\r` / `\b` rewrites first if `render_cr` is set.
+///
+/// Tabs are replayed with `tabs`, or every 8 columns when the command has none.
+fn resolve_rendered(
+    ctx: &Context,
+    text: Option<String>,
+    render_cr: bool,
+    tabs: Option<&TabStops>,
+    policy: &LayoutPolicy,
+) -> Result<String> {
+    let input = resolve_input(ctx, text)?;
+    if render_cr {
+        let stops = tabs.cloned().unwrap_or_default();
+        Ok(flatten_text(&input, &stops, policy))
+    } else {
+        Ok(input)
+    }
+}
+
+/// Resolves `--width` to columns; without it, uses the terminal width when
+/// stdout is a TTY.
+fn resolve_width(ctx: &Context, width: Option<WidthSpec>) -> Result<usize> {
+    match width {
+        Some(spec) => Ok(spec.columns()),
+        None if is_stdout_terminal() => Ok(WidthSpec::Auto.columns()),
+        None => bail!(RunefixError::new(ctx, ErrorKey::WidthRequired)),
     }
 }
 
@@ -33,41 +59,90 @@ fn parse_policy(name: &str) -> WidthPolicy {
 /// # Arguments
 /// * `ctx` - Global context, including language, style, and configuration.
 /// * `cli` - Parsed CLI arguments and subcommand variants.
+/// * `out` - Writer for command output (stdout, or a buffer in tests).
 ///
 /// # Returns
 /// * `Result<()>` - Returns `Ok` on success, or propagates errors from execution.
-pub fn dispatch(ctx: Context, cli: Cli) -> Result<()> {
+pub fn dispatch(ctx: &Context, cli: Cli, out: &mut dyn Write) -> Result<()> {
+    // Global width options apply on top of whichever policy is selected
+    let overrides = load_overrides(ctx, &cli.overrides, &cli.override_file)?;
+    let ambiguous = cli.ambiguous.map(AmbiguousWidth::resolve);
+    let unicode_version = cli.unicode_version;
+    let format = cli.format;
+    let parse_policy = |name: &str| {
+        let mut policy = parse_policy(ctx, name)?;
+        policy.overrides.extend(overrides.iter().cloned());
+        if ambiguous.is_some() {
+            policy.ambiguous = ambiguous;
+        }
+        if unicode_version.is_some() {
+            policy.unicode_version = unicode_version;
+        }
+        Ok::<_, RunefixError>(policy)
+    };
+
+    // Every command prints text; other formats only where implemented
+    let supported = match &cli.command {
+        _ if format == OutputFormat::Text => true,
+        Commands::Atoms(_) | Commands::Graphemes(_) => !format.is_source(),
+        Commands::Widths(_) => format.is_structured() || format.is_tabular(),
+        Commands::Width(_)
+        | Commands::Split(_)
+        | Commands::Truncate(_)
+        | Commands::Slice(_)
+        | Commands::Screen(_)
+        | Commands::Compare(_)
+        | Commands::Version(_) => format.is_structured(),
+        Commands::ExportTable(_) => TableFormat::from_output(format).is_some(),
+        _ => false,
+    };
+    if !supported {
+        bail!(RunefixError::with_args(
+            ctx,
+            ErrorKey::FormatUnsupported,
+            &[("format", format.name().to_string())],
+        ));
+    }
+
     match cli.command {
         // Run the `atoms` command: segments text into Unicode "atoms".
         Commands::Atoms(TextInput { text }) => {
-            let input = resolve_input(&ctx, text);
-            run_atoms(&ctx, &input);
+            let input = resolve_input(ctx, text)?;
+            run_atoms(ctx, out, &input, &parse_policy("terminal")?, format)?;
         }
 
         // Run the `graphemes` command: segments text by grapheme clusters.
         Commands::Graphemes(TextInput { text }) => {
-            let input = resolve_input(&ctx, text);
-            run_graphemes(&ctx, &input);
+            let input = resolve_input(ctx, text)?;
+            run_graphemes(ctx, out, &input, &parse_policy("terminal")?, format)?;
         }
 
         // Run the `width` command: calculate display width of entire input.
         Commands::Width(PolicyInput {
             text,
             policy,
+            tabstop,
+            render_cr,
             verbose,
         }) => {
-            let input = resolve_input(&ctx, text);
-            run_width(&ctx, &input, parse_policy(&policy), verbose);
+            let tabs = tabstop.map(|t| parse_tab_stops(ctx, &t)).transpose()?;
+            let policy = parse_policy(&policy)?;
+            let input = resolve_rendered(ctx, text, render_cr, tabs.as_ref(), &policy)?;
+            run_width(ctx, out, &input, policy, tabs.as_ref(), verbose, format)?;
         }
 
         // Run the `widths` command: compute width of each grapheme segment.
         Commands::Widths(PolicyInput {
             text,
             policy,
+            tabstop,
+            render_cr,
             verbose,
         }) => {
-            let input = resolve_input(&ctx, text);
-            run_widths(&ctx, &input, parse_policy(&policy), verbose);
+            let tabs = tabstop.map(|t| parse_tab_stops(ctx, &t)).transpose()?;
+            let policy = parse_policy(&policy)?;
+            let input = resolve_rendered(ctx, text, render_cr, tabs.as_ref(), &policy)?;
+            run_widths(ctx, out, &input, policy, tabs.as_ref(), verbose, format)?;
         }
 
         // Run the `split` command: split input based on cumulative display width.
@@ -75,10 +150,24 @@ pub fn dispatch(ctx: Context, cli: Cli) -> Result<()> {
             text,
             width,
             policy,
+            tabstop,
+            render_cr,
             verbose,
         }) => {
-            let input = resolve_input(&ctx, text);
-            run_split(&ctx, &input, width, parse_policy(&policy), verbose);
+            let width = resolve_width(ctx, width)?;
+            let tabs = tabstop.map(|t| parse_tab_stops(ctx, &t)).transpose()?;
+            let policy = parse_policy(&policy)?;
+            let input = resolve_rendered(ctx, text, render_cr, tabs.as_ref(), &policy)?;
+            run_split(
+                ctx,
+                out,
+                &input,
+                width,
+                policy,
+                tabs.as_ref(),
+                verbose,
+                format,
+            )?;
         }
 
         // Run the `truncate` command: truncate input to a fixed display width.
@@ -86,10 +175,24 @@ pub fn dispatch(ctx: Context, cli: Cli) -> Result<()> {
             text,
             width,
             policy,
+            tabstop,
+            render_cr,
             verbose,
         }) => {
-            let input = resolve_input(&ctx, text);
-            run_truncate(&ctx, &input, width, parse_policy(&policy), verbose);
+            let width = resolve_width(ctx, width)?;
+            let tabs = tabstop.map(|t| parse_tab_stops(ctx, &t)).transpose()?;
+            let policy = parse_policy(&policy)?;
+            let input = resolve_rendered(ctx, text, render_cr, tabs.as_ref(), &policy)?;
+            run_truncate(
+                ctx,
+                out,
+                &input,
+                width,
+                policy,
+                tabs.as_ref(),
+                verbose,
+                format,
+            )?;
         }
 
         // Run the `slice` command: slice text by char, grapheme, or width.
@@ -101,6 +204,7 @@ pub fn dispatch(ctx: Context, cli: Cli) -> Result<()> {
             grapheme,
             verbose,
             strict,
+            render_cr,
         }) => {
             let mode = if char {
                 SliceMode::Char
@@ -112,17 +216,215 @@ pub fn dispatch(ctx: Context, cli: Cli) -> Result<()> {
                 // Default fallback mode
                 SliceMode::Grapheme
             };
-            let input = resolve_input(&ctx, text);
-            run_slice(&ctx, &input, &range, mode, verbose, strict)?;
+            let policy = parse_policy("terminal")?;
+            let input = resolve_rendered(ctx, text, render_cr, None, &policy)?;
+            run_slice(
+                ctx, out, &input, &range, mode, &policy, verbose, strict, format,
+            )?;
+        }
+
+        // Run the `flatten` command: replay carriage returns and backspaces.
+        Commands::Flatten(FlattenArgs {
+            text,
+            tabstop,
+            policy,
+        }) => {
+            let stops = parse_tab_stops(ctx, &tabstop)?;
+            let input = resolve_input(ctx, text)?;
+            run_flatten(out, &input, &stops, parse_policy(&policy)?)?;
+        }
+
+        // Run the `expand` command: convert tabs to spaces by display column.
+        Commands::Expand(ExpandArgs {
+            text,
+            tabstop,
+            initial,
+            policy,
+        }) => {
+            let stops = parse_tab_stops(ctx, &tabstop)?;
+            let input = resolve_input(ctx, text)?;
+            run_expand(out, &input, &stops, parse_policy(&policy)?, initial)?;
+        }
+
+        // Run the `unexpand` command: convert blanks back to tabs by display column.
+        Commands::Unexpand(UnexpandArgs {
+            text,
+            tabstop,
+            all,
+            policy,
+        }) => {
+            let stops = parse_tab_stops(ctx, &tabstop)?;
+            let input = resolve_input(ctx, text)?;
+            run_unexpand(out, &input, &stops, parse_policy(&policy)?, all)?;
+        }
+
+        // Run the `box` command: frame input lines with a width-aware border.
+        Commands::Box(BoxArgs {
+            text,
+            style,
+            title,
+            padding,
+            align,
+            policy,
+        }) => {
+            let input = resolve_input(ctx, text)?;
+            run_box(
+                out,
+                &input,
+                style,
+                title.as_deref(),
+                padding,
+                align,
+                parse_policy(&policy)?,
+            )?;
+        }
+
+        // Run the `ruler` command: show column positions of each grapheme.
+        Commands::Ruler(RulerArgs {
+            text,
+            mark,
+            position,
+            policy,
+            verbose,
+        }) => {
+            let input = resolve_input(ctx, text)?;
+            run_ruler(
+                ctx,
+                out,
+                &input,
+                &mark,
+                position,
+                parse_policy(&policy)?,
+                verbose,
+            )?;
+        }
+
+        // Run the `show` command: visualize terminal cell occupancy.
+        Commands::Show(ShowArgs { text, verbose }) => {
+            let input = resolve_input(ctx, text)?;
+            run_show(ctx, out, &input, &parse_policy("terminal")?, verbose)?;
+        }
+
+        // Run the `screen` command: replay output on a virtual terminal.
+        Commands::Screen(ScreenArgs {
+            text,
+            cols,
+            rows,
+            tabstop,
+            policy,
+            frame,
+        }) => {
+            let stops = parse_tab_stops(ctx, &tabstop)?;
+            let input = resolve_input(ctx, text)?;
+            run_screen(
+                out,
+                &input,
+                cols.into(),
+                rows.into(),
+                stops,
+                parse_policy(&policy)?,
+                format,
+                frame,
+            )?;
+        }
+
+        // Run the `probe` command: measure real rendering via cursor position reports.
+        Commands::Probe(ProbeArgs {
+            emulate,
+            save,
+            name,
+            verbose,
+        }) => {
+            let emulate = emulate.map(|p| parse_policy(&p)).transpose()?;
+            run_probe(ctx, out, emulate, save.as_deref(), &name, verbose)?;
+        }
+
+        // Run the `testpattern` command: print alignment grids for eyeballing.
+        Commands::Testpattern(TestpatternArgs {
+            categories,
+            policy,
+            verbose,
+        }) => {
+            run_testpattern(ctx, out, &categories, parse_policy(&policy)?, verbose)?;
+        }
+
+        // Run the `policy diff` command: list graphemes whose width differs between policies.
+        Commands::Policy(PolicyArgs {
+            command: PolicyCommand::Diff(PolicyDiffArgs { mut args, verbose }),
+        }) => {
+            // A trailing argument that names no policy is the text to segment
+            let text = match args.last() {
+                Some(last) if !is_known_policy(ctx, last) => args.pop(),
+                _ => None,
+            };
+            if args.len() < 2 {
+                bail!(RunefixError::new(ctx, ErrorKey::PolicyDiffTooFew));
+            }
+
+            // Without text, read piped stdin; scan all code points otherwise
+            let input = resolve_optional_input(ctx, text)?;
+
+            let policies: Vec<LayoutPolicy> = args
+                .iter()
+                .map(|p| parse_policy(p))
+                .collect::<Result<_, _>>()?;
+            run_policy_diff(ctx, out, &policies, input.as_deref(), verbose)?;
+        }
+
+        // Run the `compare` command: report where another width implementation disagrees.
+        Commands::Compare(CompareArgs {
+            text,
+            against,
+            policies,
+            verbose,
+        }) => {
+            // Without text, read piped stdin; scan all code points otherwise
+            let input = resolve_optional_input(ctx, text)?;
+
+            let policies: Vec<LayoutPolicy> = policies
+                .iter()
+                .map(|p| parse_policy(p))
+                .collect::<Result<_, _>>()?;
+            run_compare(
+                ctx,
+                out,
+                against,
+                &policies,
+                input.as_deref(),
+                format,
+                verbose,
+            )?;
+        }
+
+        // Run the `export-table` command: generate a width table for other languages.
+        Commands::ExportTable(ExportTableArgs { policy, output }) => {
+            // Checked above: `--format` names a table language
+            let table_format = TableFormat::from_output(format).unwrap_or_default();
+            run_export_table(
+                out,
+                &parse_policy(&policy)?,
+                table_format,
+                output.as_deref(),
+            )?;
+        }
+
+        // Run the `unicode-diff` command: list code points whose width changed between versions.
+        Commands::UnicodeDiff(UnicodeDiffArgs {
+            from,
+            to,
+            policy,
+            verbose,
+        }) => {
+            run_unicode_diff(ctx, out, from, to, parse_policy(&policy)?, verbose)?;
         }
 
         // Run the `version` command: display version info in plain or JSON format.
         Commands::Version(VersionArgs { json }) => {
-            run_version(&ctx, json);
+            run_version(ctx, out, json, format)?;
         }
 
         // Run the `init` command: launch interactive setup for user preferences.
-        Commands::Init => run_init()?,
+        Commands::Init => run_init(ctx, out)?,
     }
 
     Ok(())
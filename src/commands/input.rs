@@ -1,31 +1,48 @@
 //! Input resolution logic for CLI commands.
 
 use crate::config::Context;
+use crate::error::RunefixError;
 use crate::i18n::keys::ErrorKey;
 use crate::style::term::is_stdin_terminal;
 use std::io::{self, Read};
-use std::process::exit;
 
 /// Resolves the input text from either CLI argument or stdin.
-pub fn resolve_input(ctx: &Context, text: Option<String>) -> String {
+///
+/// Fails if there is no argument and stdin is a terminal, or if stdin
+/// cannot be read (e.g. it is not valid UTF-8).
+pub fn resolve_input(ctx: &Context, text: Option<String>) -> Result<String, RunefixError> {
     // Use CLI argument if available
     if let Some(t) = text {
-        return t;
+        return Ok(t);
     }
 
     let mut buf = String::new();
 
-    // If no input and stdin is a terminal, show error and exit
+    // If no input and stdin is a terminal, there is nothing to read
     if is_stdin_terminal() {
-        eprintln!("{}", ctx.t(ErrorKey::InputTextNoProvided));
-        exit(1);
+        return Err(RunefixError::new(ctx, ErrorKey::InputTextNoProvided));
     }
 
     // Read entire stdin into a string buffer
-    io::stdin()
-        .read_to_string(&mut buf)
-        .expect("Failed to read from stdin");
+    io::stdin().read_to_string(&mut buf).map_err(|e| {
+        RunefixError::with_args(ctx, ErrorKey::InputUnreadable, &[("error", e.to_string())])
+    })?;
 
     // Remove trailing newline for cleaner output
-    buf.trim_end_matches('\n').to_string()
+    Ok(buf.trim_end_matches('\n').to_string())
+}
+
+/// Resolves optional input text for commands that can also scan on their own.
+///
+/// Uses the CLI argument, else piped stdin; returns `None` when stdin is a
+/// terminal or the piped input is empty.
+pub fn resolve_optional_input(
+    ctx: &Context,
+    text: Option<String>,
+) -> Result<Option<String>, RunefixError> {
+    match text {
+        Some(t) => Ok(Some(t)),
+        None if is_stdin_terminal() => Ok(None),
+        None => Ok(Some(resolve_input(ctx, None)?).filter(|s| !s.is_empty())),
+    }
 }
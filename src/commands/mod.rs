@@ -2,3 +2,11 @@ pub mod cli;
 pub mod cmds;
 pub mod dispatch;
 pub mod input;
+pub mod output;
+pub mod overrides;
+pub mod overstrike;
+pub mod policy;
+pub mod samples;
+pub mod tabs;
+pub mod unicode_age;
+pub mod vt;
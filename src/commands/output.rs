@@ -0,0 +1,214 @@
+//! Machine-readable output for the global `--format` option.
+//!
+//! `json` prints one pretty-printed document per invocation; `ndjson` prints
+//! one compact object per line (one per segment or output line), so tools can
+//! stream results instead of scraping the localized human-readable tables.
+//! `csv` and `tsv` print one row per segment for spreadsheets and databases;
+//! `html` and `svg` reports are rendered by the commands that support them.
+//! `c`, `rust`, `go` and `python` select the language of `export-table`.
+
+use crate::commands::samples::classify;
+use clap::ValueEnum;
+use serde_json::{Value, json};
+use std::io::{self, Write};
+
+/// Output format selected with `--format`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable, localized output
+    #[default]
+    Text,
+
+    /// A single JSON document
+    Json,
+
+    /// One JSON object per line
+    Ndjson,
+
+    /// Comma-separated rows, one per segment (RFC 4180 quoting)
+    Csv,
+
+    /// Tab-separated rows, one per segment (backslash escapes)
+    Tsv,
+
+    /// Standalone HTML report with a cell grid and legend
+    Html,
+
+    /// Standalone SVG image of the same report
+    Svg,
+
+    /// C source (`export-table`)
+    C,
+
+    /// Rust source (`export-table`)
+    Rust,
+
+    /// Go source (`export-table`)
+    Go,
+
+    /// Python source (`export-table`)
+    Python,
+}
+
+impl OutputFormat {
+    /// Returns true for the machine-readable formats.
+    pub fn is_structured(self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::Ndjson)
+    }
+
+    /// Returns true for the row-per-segment formats.
+    pub fn is_tabular(self) -> bool {
+        matches!(self, OutputFormat::Csv | OutputFormat::Tsv)
+    }
+
+    /// Returns true for the rendered report formats.
+    pub fn is_visual(self) -> bool {
+        matches!(self, OutputFormat::Html | OutputFormat::Svg)
+    }
+
+    /// Returns true for the source-code languages of `export-table`.
+    pub fn is_source(self) -> bool {
+        matches!(
+            self,
+            OutputFormat::C | OutputFormat::Rust | OutputFormat::Go | OutputFormat::Python
+        )
+    }
+
+    /// Returns the name accepted by `--format`.
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Ndjson => "ndjson",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Tsv => "tsv",
+            OutputFormat::Html => "html",
+            OutputFormat::Svg => "svg",
+            OutputFormat::C => "c",
+            OutputFormat::Rust => "rust",
+            OutputFormat::Go => "go",
+            OutputFormat::Python => "python",
+        }
+    }
+}
+
+/// Returns the code points of `s` as `U+XXXX` strings (e.g. `U+1F600`).
+pub fn code_points(s: &str) -> Vec<String> {
+    s.chars().map(|c| format!("U+{:04X}", c as u32)).collect()
+}
+
+/// Describes one segment of `input` with its offsets, code points and width.
+///
+/// Offsets are located by searching `input` from `*cursor` (a byte offset),
+/// which is advanced past the segment; `end` offsets are exclusive.
+pub fn segment(input: &str, cursor: &mut usize, index: usize, text: &str, width: usize) -> Value {
+    let byte_start = input[*cursor..].find(text).map_or(*cursor, |i| *cursor + i);
+    let byte_end = byte_start + text.len();
+    *cursor = byte_end;
+
+    let char_start = input[..byte_start].chars().count();
+    json!({
+        "index": index,
+        "text": text,
+        "byte_start": byte_start,
+        "byte_end": byte_end,
+        "char_start": char_start,
+        "char_end": char_start + text.chars().count(),
+        "code_points": code_points(text),
+        "width": width,
+    })
+}
+
+/// Describes a list of `(text, width)` segments of `input`.
+pub fn segments<'a>(input: &str, parts: impl IntoIterator<Item = (&'a str, usize)>) -> Vec<Value> {
+    let mut cursor = 0;
+    parts
+        .into_iter()
+        .enumerate()
+        .map(|(i, (text, width))| segment(input, &mut cursor, i, text, width))
+        .collect()
+}
+
+/// Prints a structured report.
+///
+/// With `json`, `fields` is printed as one document, with `items` (if any)
+/// added under its key. With `ndjson`, each item is printed on its own line,
+/// or `fields` alone when the command has no items.
+pub fn print_report(
+    out: &mut dyn Write,
+    format: OutputFormat,
+    mut fields: Value,
+    items: Option<(&str, Vec<Value>)>,
+) -> io::Result<()> {
+    match (format, items) {
+        (OutputFormat::Ndjson, Some((_, items))) => {
+            for item in items {
+                writeln!(out, "{item}")?;
+            }
+            Ok(())
+        }
+        (OutputFormat::Ndjson, None) => writeln!(out, "{fields}"),
+        (_, items) => {
+            if let (Some((key, items)), Some(map)) = (items, fields.as_object_mut()) {
+                map.insert(key.to_string(), Value::Array(items));
+            }
+            writeln!(out, "{fields:#}")
+        }
+    }
+}
+
+/// Column names of the segment table printed by [`print_segment_table`].
+const SEGMENT_COLUMNS: [&str; 5] = ["index", "segment", "code_points", "width", "category"];
+
+/// Quotes a CSV field if it contains a delimiter, quote or control character.
+fn csv_field(s: &str) -> String {
+    if s.contains(|c: char| c == ',' || c == '"' || c.is_control()) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Escapes a TSV field: backslash, tab, newline and carriage return become
+/// `\\`, `\t`, `\n`, `\r`; other control characters become `\xNN`.
+fn tsv_field(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c if c.is_ascii_control() => out.push_str(&format!("\\x{:02X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Prints `(text, width)` segments as CSV or TSV rows under a header of
+/// [`SEGMENT_COLUMNS`]. The category column is a hint and may be empty.
+pub fn print_segment_table<'a>(
+    out: &mut dyn Write,
+    format: OutputFormat,
+    parts: impl IntoIterator<Item = (&'a str, usize)>,
+) -> io::Result<()> {
+    let (sep, field): (&str, fn(&str) -> String) = match format {
+        OutputFormat::Tsv => ("\t", tsv_field),
+        _ => (",", csv_field),
+    };
+
+    writeln!(out, "{}", SEGMENT_COLUMNS.join(sep))?;
+    for (i, (text, width)) in parts.into_iter().enumerate() {
+        let category = classify(text).map_or("", |c| c.name());
+        let row = [
+            i.to_string(),
+            field(text),
+            code_points(text).join(" "),
+            width.to_string(),
+            category.to_string(),
+        ];
+        writeln!(out, "{}", row.join(sep))?;
+    }
+    Ok(())
+}
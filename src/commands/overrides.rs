@@ -0,0 +1,119 @@
+//! Per-code-point width overrides from the command line.
+//!
+//! Nerd Font and Powerline glyphs live in the Private Use Area, where the
+//! rendered width depends on the font rather than on Unicode data. Overrides
+//! pin those code points to a fixed width on top of any policy.
+
+use crate::commands::policy::WidthOverride;
+use crate::config::Context;
+use crate::error::RunefixError;
+use crate::i18n::keys::ErrorKey;
+use anyhow::{Result, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Powerline separators and symbols (always one cell, so prompts join up).
+const POWERLINE: &[(u32, u32)] = &[(0xE0A0, 0xE0A3), (0xE0B0, 0xE0D7)];
+
+/// Nerd Fonts v3 icon sets (Pomicons, Font Awesome, Weather, Seti-UI,
+/// Devicons, Codicons, Font Logos, Octicons, Material Design).
+const ICONS: &[(u32, u32)] = &[
+    (0xE000, 0xE00A),
+    (0xE200, 0xE2A9),
+    (0xE300, 0xE3E3),
+    (0xE5FA, 0xE6B7),
+    (0xE700, 0xE8EF),
+    (0xEA60, 0xEC1E),
+    (0xED00, 0xF2FF),
+    (0xF300, 0xF381),
+    (0xF400, 0xF533),
+    (0xF0001, 0xF1AF0),
+];
+
+/// Returns the overrides of a bundled preset.
+///
+/// `nerd-fonts` treats icons as two cells (as drawn by the proportional
+/// "Nerd Font" variants); `nerd-fonts-mono` keeps everything at one cell.
+fn preset(name: &str) -> Option<Vec<WidthOverride>> {
+    let icon_width = match name {
+        "nerd-fonts" => 2,
+        "nerd-fonts-mono" => 1,
+        _ => return None,
+    };
+
+    let ranges = |table: &[(u32, u32)], width| {
+        table
+            .iter()
+            .filter_map(move |&(start, end)| {
+                Some(WidthOverride {
+                    start: char::from_u32(start)?,
+                    end: char::from_u32(end)?,
+                    width,
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut overrides = ranges(ICONS, icon_width);
+    overrides.extend(ranges(POWERLINE, 1));
+    Some(overrides)
+}
+
+/// Parses `RANGE=WIDTH` (e.g. `U+E0B0=1`, `U+E0A0..U+E0D4=2`).
+fn parse_entry(entry: &str) -> Option<WidthOverride> {
+    let (range, width) = entry.split_once('=')?;
+    WidthOverride::parse_range(range, width.trim().parse().ok()?)
+}
+
+/// Parses one `--override` value: a `RANGE=WIDTH` entry or a preset name.
+fn parse_override(ctx: &Context, spec: &str) -> Result<Vec<WidthOverride>> {
+    if let Some(overrides) = preset(spec.trim()) {
+        return Ok(overrides);
+    }
+
+    match parse_entry(spec) {
+        Some(o) => Ok(vec![o]),
+        None => bail!(RunefixError::with_args(
+            ctx,
+            ErrorKey::OverrideInvalid,
+            &[("spec", spec.to_string())],
+        )),
+    }
+}
+
+/// Reads an override file: one `RANGE = WIDTH` entry (or preset name) per line,
+/// with `#` comments.
+fn load_override_file(ctx: &Context, path: &Path) -> Result<Vec<WidthOverride>> {
+    let Ok(content) = fs::read_to_string(path) else {
+        bail!(RunefixError::with_args(
+            ctx,
+            ErrorKey::OverrideFileUnreadable,
+            &[("path", path.display().to_string())],
+        ));
+    };
+
+    content
+        .lines()
+        .map(|l| l.split('#').next().unwrap_or_default().trim())
+        .filter(|l| !l.is_empty())
+        .map(|l| parse_override(ctx, l))
+        .collect::<Result<Vec<_>>>()
+        .map(|v| v.into_iter().flatten().collect())
+}
+
+/// Collects overrides from files first, then from `--override` values,
+/// so that explicit command-line entries take precedence.
+pub fn load_overrides(
+    ctx: &Context,
+    specs: &[String],
+    files: &[PathBuf],
+) -> Result<Vec<WidthOverride>> {
+    let mut overrides = Vec::new();
+    for path in files {
+        overrides.extend(load_override_file(ctx, path)?);
+    }
+    for spec in specs {
+        overrides.extend(parse_override(ctx, spec)?);
+    }
+    Ok(overrides)
+}
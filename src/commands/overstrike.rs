@@ -0,0 +1,208 @@
+//! Carriage-return and overstrike emulation for captured terminal output.
+//!
+//! Progress bars and spinners rewrite a line with `\r`, `\b` and erase-in-line
+//! sequences. This module replays those edits on a cell buffer, the way a
+//! terminal would, and returns the text that remains visible.
+
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::tabs::TabStops;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Furthest a cursor move can reach past the written text. Escape sequences
+/// asking for more (e.g. `ESC[99999999999C`) are clamped to it.
+const MAX_CURSOR_JUMP: usize = 4096;
+
+/// A single terminal cell in the line buffer.
+#[derive(Clone)]
+enum Cell {
+    // Never written or erased
+    Empty,
+
+    // First cell of a grapheme (plus any zero-width marks written after it)
+    Head(String),
+
+    // Trailing cell covered by a wide grapheme
+    Continuation,
+}
+
+/// Line buffer with a cursor, mimicking a single terminal row without a right margin.
+struct LineBuffer<'p> {
+    cells: Vec<Cell>,
+    cursor: usize,
+    policy: &'p LayoutPolicy,
+}
+
+impl<'p> LineBuffer<'p> {
+    fn new(policy: &'p LayoutPolicy) -> Self {
+        Self {
+            cells: Vec::new(),
+            cursor: 0,
+            policy,
+        }
+    }
+
+    /// Blank out the whole grapheme covering `col`, if any.
+    fn clear_at(&mut self, col: usize) {
+        if col >= self.cells.len() {
+            return;
+        }
+
+        // Walk back to the head of a wide grapheme
+        let mut head = col;
+        while head > 0 && matches!(self.cells[head], Cell::Continuation) {
+            head -= 1;
+        }
+
+        self.cells[head] = Cell::Empty;
+        let mut i = head + 1;
+        while i < self.cells.len() && matches!(self.cells[i], Cell::Continuation) {
+            self.cells[i] = Cell::Empty;
+            i += 1;
+        }
+    }
+
+    /// Move the cursor to `col`, at most [`MAX_CURSOR_JUMP`] cells past the buffer.
+    fn move_to(&mut self, col: usize) {
+        self.cursor = col.min(self.cells.len().saturating_add(MAX_CURSOR_JUMP));
+    }
+
+    /// Write a printable grapheme at the cursor and advance it.
+    fn put(&mut self, g: &str) {
+        let w = self.policy.display_width(g);
+
+        // Zero-width clusters attach to the previous cell
+        if w == 0 {
+            let prev = self
+                .cursor
+                .checked_sub(1)
+                .and_then(|c| self.cells.get_mut(c));
+            if let Some(Cell::Head(prev)) = prev {
+                prev.push_str(g);
+            }
+            return;
+        }
+
+        let end = self.cursor.saturating_add(w);
+        if self.cells.len() < end {
+            self.cells.resize(end, Cell::Empty);
+        }
+
+        // Overwriting part of a wide grapheme erases the rest of it
+        for col in self.cursor..end {
+            self.clear_at(col);
+        }
+
+        self.cells[self.cursor] = Cell::Head(g.to_string());
+        for col in self.cursor + 1..end {
+            self.cells[col] = Cell::Continuation;
+        }
+        self.cursor = end;
+    }
+
+    /// Erase cells in `start..end` (clamped to the buffer).
+    fn erase(&mut self, start: usize, end: usize) {
+        for col in start..end.min(self.cells.len()) {
+            self.clear_at(col);
+        }
+    }
+
+    /// Apply a CSI sequence with the given parameters and final byte.
+    fn csi(&mut self, params: &str, action: char) {
+        let n = params
+            .split(';')
+            .next()
+            .and_then(|p| p.parse::<usize>().ok());
+
+        match action {
+            // Erase in line: 0 = to end, 1 = to start, 2 = whole line
+            'K' => match n.unwrap_or(0) {
+                0 => self.erase(self.cursor, usize::MAX),
+                1 => self.erase(0, self.cursor.saturating_add(1)),
+                _ => self.erase(0, usize::MAX),
+            },
+            // Cursor forward / back
+            'C' => self.move_to(self.cursor.saturating_add(n.unwrap_or(1).max(1))),
+            'D' => self.cursor = self.cursor.saturating_sub(n.unwrap_or(1).max(1)),
+            // Cursor horizontal absolute (1-based)
+            'G' => self.move_to(n.unwrap_or(1).saturating_sub(1)),
+            // SGR and anything else do not affect visible text
+            _ => {}
+        }
+    }
+
+    /// Render the visible line, dropping trailing empty cells.
+    fn render(&self) -> String {
+        let used = self
+            .cells
+            .iter()
+            .rposition(|c| !matches!(c, Cell::Empty))
+            .map_or(0, |i| i + 1);
+
+        self.cells[..used]
+            .iter()
+            .map(|c| match c {
+                Cell::Empty => " ",
+                Cell::Head(g) => g.as_str(),
+                Cell::Continuation => "",
+            })
+            .collect()
+    }
+}
+
+/// Replay `\r`, `\b`, tabs and erase-in-line sequences on a single line
+/// and return the final visible text. Other escape sequences are dropped.
+pub fn flatten_line(line: &str, stops: &TabStops, policy: &LayoutPolicy) -> String {
+    let mut buf = LineBuffer::new(policy);
+    let mut rest = line;
+
+    while !rest.is_empty() {
+        // Escape sequences: CSI (`ESC [ ... final`), OSC (`ESC ] ... BEL/ST`), or `ESC x`
+        if let Some(after) = rest.strip_prefix('\u{1B}') {
+            rest = if let Some(body) = after.strip_prefix('[') {
+                let end = body
+                    .find(|c: char| ('\u{40}'..='\u{7E}').contains(&c))
+                    .unwrap_or(body.len());
+                if let Some(action) = body[end..].chars().next() {
+                    buf.csi(&body[..end], action);
+                    &body[end + action.len_utf8()..]
+                } else {
+                    ""
+                }
+            } else if let Some(body) = after.strip_prefix(']') {
+                match body.find(['\u{07}', '\u{1B}']) {
+                    Some(i) if body[i..].starts_with("\u{1B}\\") => &body[i + 2..],
+                    Some(i) => &body[i + 1..],
+                    None => "",
+                }
+            } else {
+                let mut chars = after.chars();
+                chars.next();
+                chars.as_str()
+            };
+            continue;
+        }
+
+        // Plain text up to the next escape
+        let end = rest.find('\u{1B}').unwrap_or(rest.len());
+        for g in rest[..end].graphemes(true) {
+            match g {
+                "\r" => buf.cursor = 0,
+                "\u{8}" => buf.cursor = buf.cursor.saturating_sub(1),
+                "\t" => buf.move_to(stops.next_stop(buf.cursor)),
+                g if g.chars().all(char::is_control) => {}
+                g => buf.put(g),
+            }
+        }
+        rest = &rest[end..];
+    }
+
+    buf.render()
+}
+
+/// Flatten every line of `text` (see [`flatten_line`]).
+pub fn flatten_text(text: &str, stops: &TabStops, policy: &LayoutPolicy) -> String {
+    text.lines()
+        .map(|line| flatten_line(line, stops, policy))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
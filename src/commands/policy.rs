@@ -0,0 +1,458 @@
+//! Width policy lookup by name.
+//!
+//! A [`LayoutPolicy`] wraps the core `WidthPolicy` categories (emoji, CJK,
+//! fullwidth variants, fallback) with the rendering quirks that differ between
+//! terminal emulators: ZWJ sequence joining, VS16 widening, East Asian
+//! Ambiguous characters and regional indicator flags.
+//!
+//! Besides the built-in names, users can declare their own policies as
+//! `[policy.<name>]` sections in the config file (see `config::settings`).
+
+use crate::commands::unicode_age::{UnicodeVersion, age, parse_unicode_version};
+use crate::config::{Context, PolicySection};
+use crate::error::RunefixError;
+use crate::i18n::keys::ErrorKey;
+use clap::ValueEnum;
+use runefix_core::WidthPolicy;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
+
+/// Names of the built-in policies, in display order.
+pub const BUILTIN_POLICIES: [&str; 3] = ["terminal", "markdown", "compact"];
+
+/// Names of the built-in terminal emulator profiles, in display order.
+pub const PROFILES: [&str; 8] = [
+    "xterm",
+    "vte",
+    "kitty",
+    "wezterm",
+    "alacritty",
+    "windows-terminal",
+    "tmux",
+    "iterm2",
+];
+
+const ZWJ: char = '\u{200D}';
+const VS16: char = '\u{FE0F}';
+const KEYCAP: char = '\u{20E3}';
+
+/// A fixed width for a range of code points (e.g. Nerd Font glyphs).
+#[derive(Debug, Clone)]
+pub struct WidthOverride {
+    pub start: char,
+    pub end: char,
+    pub width: usize,
+}
+
+impl WidthOverride {
+    /// Parses `U+XXXX` or `U+XXXX..U+YYYY` (the `U+` prefix is optional).
+    pub fn parse_range(spec: &str, width: usize) -> Option<Self> {
+        let code = |s: &str| {
+            let s = s.trim();
+            let hex = s
+                .strip_prefix("U+")
+                .or_else(|| s.strip_prefix("u+"))
+                .unwrap_or(s);
+            u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+        };
+
+        let (start, end) = match spec.split_once("..") {
+            Some((a, b)) => (code(a)?, code(b)?),
+            None => (code(spec)?, code(spec)?),
+        };
+
+        (start <= end).then_some(Self { start, end, width })
+    }
+
+    /// Returns true if `c` falls inside this range.
+    fn contains(&self, c: char) -> bool {
+        (self.start..=self.end).contains(&c)
+    }
+}
+
+/// A named width policy, including how the target renders emoji sequences.
+#[derive(Debug, Clone)]
+pub struct LayoutPolicy {
+    /// Name the policy was selected by (e.g. `terminal`, `kitty`)
+    pub name: String,
+
+    /// Per-category widths resolved by `runefix-core`
+    pub base: WidthPolicy,
+
+    /// Render ZWJ sequences as one glyph; otherwise each component is drawn separately
+    pub zwj_joined: bool,
+
+    /// Widen text-presentation characters followed by VS16 to emoji width
+    pub vs16_wide: bool,
+
+    /// Width of East Asian Ambiguous characters (`None` keeps the core classification)
+    pub ambiguous: Option<usize>,
+
+    /// Width of each regional indicator when flags are not drawn as one glyph
+    /// (`None` keeps flags joined at emoji width)
+    pub regional: Option<usize>,
+
+    /// Width of control characters other than line breaks (`None` keeps 0)
+    pub control: Option<usize>,
+
+    /// Per-range widths, checked before everything else (later entries win)
+    pub overrides: Vec<WidthOverride>,
+
+    /// Unicode version of the target's tables (`None` uses the core version);
+    /// newer code points are drawn as one-column tofu
+    pub unicode_version: Option<UnicodeVersion>,
+}
+
+impl LayoutPolicy {
+    /// Wraps a core policy without any emulator-specific adjustments.
+    pub fn from_core(name: &str, base: WidthPolicy) -> Self {
+        Self {
+            name: name.to_string(),
+            base,
+            zwj_joined: true,
+            vs16_wide: true,
+            ambiguous: None,
+            regional: None,
+            control: None,
+            overrides: Vec::new(),
+            unicode_version: None,
+        }
+    }
+
+    /// Standard terminal policy (see `WidthPolicy::terminal`).
+    pub fn terminal() -> Self {
+        Self::from_core("terminal", WidthPolicy::terminal())
+    }
+
+    /// Returns the display width of a single grapheme cluster.
+    pub fn width(&self, g: &str) -> usize {
+        if let Some(first) = g.chars().next() {
+            if let Some(o) = self.overrides.iter().rev().find(|o| o.contains(first)) {
+                return o.width;
+            }
+        }
+
+        if let Some(w) = self.control {
+            if !matches!(g, "\n" | "\r\n") && g.chars().all(char::is_control) {
+                return w;
+            }
+        }
+
+        // Code points the target does not know yet each take one cell of tofu;
+        // unassigned ones are left to the policy, as no version knows them
+        if let Some(version) = self.unicode_version {
+            let (unknown, known): (String, String) = g
+                .chars()
+                .partition(|&c| age(c).is_some_and(|a| a > version));
+            if !unknown.is_empty() {
+                let rest = if known.is_empty() {
+                    0
+                } else {
+                    self.width(&known)
+                };
+                return unknown.chars().count() + rest;
+            }
+        }
+
+        // Emulators without ZWJ support draw each component on its own
+        if !self.zwj_joined && g.contains(ZWJ) {
+            return g.split(ZWJ).map(|part| self.width(part)).sum();
+        }
+
+        if let Some(w) = self.regional {
+            if !g.is_empty() && g.chars().all(is_regional_indicator) {
+                return g.chars().count() * w;
+            }
+        }
+
+        // VS16 (and keycaps) on a narrow base keep the base width
+        if !self.vs16_wide && g.contains(VS16) {
+            let mut base = g.chars().filter(|&c| c != VS16 && c != KEYCAP);
+            if let (Some(c), None) = (base.next(), base.next()) {
+                if c.width() == Some(1) {
+                    return 1;
+                }
+            }
+        }
+
+        if let Some(w) = self.ambiguous {
+            let mut chars = g.chars();
+            if let (Some(c), None) = (chars.next(), chars.next()) {
+                if is_ambiguous(c) {
+                    return w;
+                }
+            }
+        }
+
+        self.base.resolve_width(g)
+    }
+
+    /// Same as `display_width_with_policy`.
+    pub fn display_width(&self, s: &str) -> usize {
+        s.graphemes(true).map(|g| self.width(g)).sum()
+    }
+
+    /// Same as `grapheme_widths_with_policy`.
+    pub fn grapheme_widths<'a>(&self, s: &'a str) -> Vec<(&'a str, usize)> {
+        s.graphemes(true).map(|g| (g, self.width(g))).collect()
+    }
+
+    /// Same as `truncate_by_width_with_policy`.
+    pub fn truncate_by_width<'a>(&self, s: &'a str, max_width: usize) -> &'a str {
+        let mut total = 0;
+        let mut end = 0;
+
+        for g in s.graphemes(true) {
+            let w = self.width(g);
+            if total + w > max_width {
+                break;
+            }
+            total += w;
+            end += g.len();
+        }
+
+        &s[..end]
+    }
+
+    /// Same as `split_by_width_with_policy`.
+    pub fn split_by_width(&self, s: &str, max_width: usize) -> Vec<String> {
+        let mut result = Vec::new();
+        let mut line = String::new();
+        let mut width = 0;
+
+        for g in s.graphemes(true) {
+            let w = self.width(g);
+            if width + w > max_width && !line.is_empty() {
+                result.push(std::mem::take(&mut line));
+                width = 0;
+            }
+            line.push_str(g);
+            width += w;
+        }
+
+        if !line.is_empty() {
+            result.push(line);
+        }
+
+        result
+    }
+}
+
+/// How to size East Asian Ambiguous characters (`±`, `→`, `○`, Greek, Cyrillic).
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum AmbiguousWidth {
+    Narrow,
+    Wide,
+    Auto,
+}
+
+impl AmbiguousWidth {
+    /// Resolves to a column count.
+    ///
+    /// `auto` honors `RUNEFIX_AMBIGUOUS` (`narrow` / `wide`) first, then treats
+    /// CJK locales (`zh`, `ja`, `ko` in `LC_ALL`, `LC_CTYPE` or `LANG`) as wide.
+    pub fn resolve(self) -> usize {
+        match self {
+            AmbiguousWidth::Narrow => 1,
+            AmbiguousWidth::Wide => 2,
+            AmbiguousWidth::Auto => match std::env::var("RUNEFIX_AMBIGUOUS").as_deref() {
+                Ok("narrow") => 1,
+                Ok("wide") => 2,
+                _ if locale_is_cjk() => 2,
+                _ => 1,
+            },
+        }
+    }
+}
+
+/// Returns true if the effective `LC_CTYPE` locale is Chinese, Japanese or Korean.
+fn locale_is_cjk() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|k| std::env::var(k).ok())
+        .find(|v| !v.is_empty())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    ["zh", "ja", "ko"].iter().any(|l| locale.starts_with(l))
+}
+
+/// Returns true for regional indicator symbols (`🇦`..`🇿`).
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+/// Greek and Cyrillic letters listed as Ambiguous in `EastAsianWidth.txt`,
+/// which `unicode-width` reports as narrow even in CJK mode.
+const AMBIGUOUS_LETTERS: &[(char, char)] = &[
+    ('\u{0391}', '\u{03A1}'),
+    ('\u{03A3}', '\u{03A9}'),
+    ('\u{03B1}', '\u{03C1}'),
+    ('\u{03C3}', '\u{03C9}'),
+    ('\u{0401}', '\u{0401}'),
+    ('\u{0410}', '\u{044F}'),
+    ('\u{0451}', '\u{0451}'),
+];
+
+/// Returns true for East Asian Ambiguous characters (narrow, but wide in CJK contexts).
+pub fn is_ambiguous(c: char) -> bool {
+    (c.width() == Some(1) && c.width_cjk() == Some(2))
+        || AMBIGUOUS_LETTERS
+            .iter()
+            .any(|&(start, end)| (start..=end).contains(&c))
+}
+
+/// Returns the emulator profile matching a name, if any.
+///
+/// Profiles approximate each emulator's default configuration.
+fn profile(name: &str) -> Option<LayoutPolicy> {
+    // (zwj_joined, vs16_wide, regional)
+    let (zwj_joined, vs16_wide, regional) = match name {
+        "xterm" => (false, false, Some(1)),
+        "vte" => (false, false, Some(1)),
+        "kitty" => (true, true, None),
+        "wezterm" => (true, true, None),
+        "alacritty" => (false, false, Some(1)),
+        "windows-terminal" => (true, true, Some(2)),
+        "tmux" => (false, true, Some(1)),
+        "iterm2" => (true, true, None),
+        _ => return None,
+    };
+
+    Some(LayoutPolicy {
+        name: name.to_string(),
+        base: WidthPolicy::terminal(),
+        zwj_joined,
+        vs16_wide,
+        ambiguous: Some(1),
+        regional,
+        control: None,
+        overrides: Vec::new(),
+        unicode_version: None,
+    })
+}
+
+/// Guesses the running terminal emulator from environment variables.
+///
+/// Multiplexers win over the outer terminal, since they decide cell layout.
+pub fn detect_profile() -> Option<&'static str> {
+    let var = |k: &str| std::env::var(k).unwrap_or_default();
+    let has = |k: &str| std::env::var_os(k).is_some();
+
+    let term = var("TERM");
+    let program = var("TERM_PROGRAM");
+
+    if has("TMUX") || program == "tmux" || term.starts_with("tmux") {
+        Some("tmux")
+    } else if has("KITTY_WINDOW_ID") || term == "xterm-kitty" {
+        Some("kitty")
+    } else if program == "WezTerm" || has("WEZTERM_EXECUTABLE") {
+        Some("wezterm")
+    } else if program == "iTerm.app" || has("ITERM_SESSION_ID") {
+        Some("iterm2")
+    } else if has("WT_SESSION") {
+        Some("windows-terminal")
+    } else if has("ALACRITTY_WINDOW_ID") || term == "alacritty" {
+        Some("alacritty")
+    } else if has("VTE_VERSION") {
+        Some("vte")
+    } else if term.starts_with("xterm") {
+        Some("xterm")
+    } else {
+        None
+    }
+}
+
+/// Resolves a built-in policy or emulator profile by name.
+fn builtin(name: &str) -> Option<LayoutPolicy> {
+    match name {
+        "terminal" => Some(LayoutPolicy::terminal()),
+        "markdown" => Some(LayoutPolicy::from_core(name, WidthPolicy::markdown())),
+        "compact" => Some(LayoutPolicy::from_core(name, WidthPolicy::compact())),
+        "auto" => Some(
+            detect_profile()
+                .and_then(profile)
+                .unwrap_or_else(LayoutPolicy::terminal),
+        ),
+        _ => profile(name),
+    }
+}
+
+/// Builds a user-defined policy from its config section.
+///
+/// Starts from `base` (a built-in policy or profile, `terminal` by default)
+/// and applies the remaining entries on top. Invalid entries are skipped
+/// with a warning.
+fn custom_policy(section: &PolicySection) -> LayoutPolicy {
+    let base = section
+        .entries
+        .iter()
+        .find(|(k, _)| k == "base")
+        .map(|(_, v)| v.as_str());
+
+    let mut policy = base
+        .and_then(builtin)
+        .unwrap_or_else(LayoutPolicy::terminal);
+    policy.name = section.name.clone();
+
+    let warn = |key: &str, value: &str| {
+        eprintln!(
+            "⚠ Ignoring invalid entry '{key} = {value}' in policy '{}'.",
+            section.name
+        );
+    };
+
+    for (key, value) in &section.entries {
+        let width = value.parse::<usize>().ok();
+        match (key.as_str(), width) {
+            ("base", _) if base.and_then(builtin).is_some() => {}
+            ("emoji", Some(w)) => policy.base.emoji = w,
+            ("cjk", Some(w)) => policy.base.cjk = w,
+            ("variant", Some(w)) => policy.base.variant = w,
+            ("fallback", Some(w)) => policy.base.fallback = w,
+            ("ambiguous", Some(w)) => policy.ambiguous = Some(w),
+            ("regional", Some(w)) => policy.regional = Some(w),
+            ("control", Some(w)) => policy.control = Some(w),
+            ("zwj", _) if value == "joined" => policy.zwj_joined = true,
+            ("zwj", _) if value == "split" => policy.zwj_joined = false,
+            ("vs16", _) if value == "wide" => policy.vs16_wide = true,
+            ("vs16", _) if value == "narrow" => policy.vs16_wide = false,
+            ("unicode", _) if parse_unicode_version(value).is_ok() => {
+                policy.unicode_version = parse_unicode_version(value).ok();
+            }
+            (range, Some(w)) => match WidthOverride::parse_range(range, w) {
+                Some(o) => policy.overrides.push(o),
+                None => warn(key, value),
+            },
+            _ => warn(key, value),
+        }
+    }
+
+    policy
+}
+
+/// Returns true if `name` is a built-in policy, profile, or configured policy.
+pub fn is_known_policy(ctx: &Context, name: &str) -> bool {
+    builtin(name).is_some() || ctx.policies.iter().any(|p| p.name == name)
+}
+
+/// Parses a policy name string into a `LayoutPolicy` object.
+///
+/// Accepts the built-in policies, emulator profiles, `auto` (detect the
+/// profile from the environment, or `terminal` when unknown), and policies
+/// defined in the config file. Built-in names take precedence.
+/// Fails with `RF0003` if the name is unknown.
+pub fn parse_policy(ctx: &Context, name: &str) -> Result<LayoutPolicy, RunefixError> {
+    if let Some(policy) = builtin(name) {
+        return Ok(policy);
+    }
+
+    match ctx.policies.iter().find(|p| p.name == name) {
+        Some(section) => Ok(custom_policy(section)),
+        None => Err(RunefixError::with_args(
+            ctx,
+            ErrorKey::PolicyUnknown,
+            &[("name", name.to_string())],
+        )),
+    }
+}
@@ -0,0 +1,267 @@
+//! Curated grapheme samples for probing and eyeballing terminal rendering.
+//!
+//! Categories follow the classes `runefix` distinguishes elsewhere (CJK,
+//! emoji, ZWJ sequences, skin tones, hair components, keycaps, ...), plus
+//! ambiguous-width and private-use glyphs where terminals commonly disagree.
+
+use crate::commands::policy::is_ambiguous;
+use crate::commands::unicode_age::age;
+use clap::ValueEnum;
+use std::collections::HashSet;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
+
+/// Category of a sample grapheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SampleCategory {
+    Ascii,
+    Cjk,
+    Kana,
+    Hangul,
+    Fullwidth,
+    Ambiguous,
+    Emoji,
+    TextEmoji,
+    EmojiVariant,
+    Zwj,
+    SkinTone,
+    HairColors,
+    Flag,
+    Keycap,
+    Combining,
+    BoxDrawing,
+    PrivateUse,
+}
+
+impl SampleCategory {
+    /// Human-readable label (matches the hint names used by `atoms`).
+    pub fn label(self) -> &'static str {
+        match self {
+            SampleCategory::Ascii => "ASCII",
+            SampleCategory::Cjk => "CJK",
+            SampleCategory::Kana => "Kana",
+            SampleCategory::Hangul => "Hangul",
+            SampleCategory::Fullwidth => "Fullwidth",
+            SampleCategory::Ambiguous => "Ambiguous",
+            SampleCategory::Emoji => "Emoji",
+            SampleCategory::TextEmoji => "Text Emoji",
+            SampleCategory::EmojiVariant => "Emoji Variant",
+            SampleCategory::Zwj => "ZWJ",
+            SampleCategory::SkinTone => "Skin Tone",
+            SampleCategory::HairColors => "Hair Colors",
+            SampleCategory::Flag => "Flag",
+            SampleCategory::Keycap => "Combining Mark",
+            SampleCategory::Combining => "Combining",
+            SampleCategory::BoxDrawing => "Box Drawing",
+            SampleCategory::PrivateUse => "Private Use",
+        }
+    }
+
+    /// Stable machine-readable name (as accepted by `--category`).
+    pub fn name(self) -> &'static str {
+        match self {
+            SampleCategory::Ascii => "ascii",
+            SampleCategory::Cjk => "cjk",
+            SampleCategory::Kana => "kana",
+            SampleCategory::Hangul => "hangul",
+            SampleCategory::Fullwidth => "fullwidth",
+            SampleCategory::Ambiguous => "ambiguous",
+            SampleCategory::Emoji => "emoji",
+            SampleCategory::TextEmoji => "text-emoji",
+            SampleCategory::EmojiVariant => "emoji-variant",
+            SampleCategory::Zwj => "zwj",
+            SampleCategory::SkinTone => "skin-tone",
+            SampleCategory::HairColors => "hair-colors",
+            SampleCategory::Flag => "flag",
+            SampleCategory::Keycap => "keycap",
+            SampleCategory::Combining => "combining",
+            SampleCategory::BoxDrawing => "box-drawing",
+            SampleCategory::PrivateUse => "private-use",
+        }
+    }
+}
+
+/// Guesses the category of an arbitrary grapheme (or atom), as a hint for
+/// tabular output. Returns `None` for controls and anything unclassified.
+pub fn classify(g: &str) -> Option<SampleCategory> {
+    let first = g.chars().next()?;
+    let has = |f: fn(char) -> bool| g.chars().any(f);
+
+    // Sequences first: the joiners and modifiers decide the layout
+    if has(|c| c == '\u{20E3}') {
+        return Some(Keycap);
+    }
+    if has(|c| ('\u{1F9B0}'..='\u{1F9B3}').contains(&c)) {
+        return Some(HairColors);
+    }
+    if has(|c| c == '\u{200D}') {
+        return Some(Zwj);
+    }
+    if has(|c| TONES.contains(&c)) {
+        return Some(SkinTone);
+    }
+    if g.chars().all(|c| ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)) {
+        return Some(Flag);
+    }
+    if has(|c| c == '\u{FE0F}') {
+        return Some(EmojiVariant);
+    }
+    if g.chars().count() > 1 {
+        return Some(Combining);
+    }
+
+    // Single code points, by block
+    let category = match first as u32 {
+        _ if first.is_control() => return None,
+        0x20..=0x7E => Ascii,
+        0x1100..=0x11FF | 0xAC00..=0xD7AF => Hangul,
+        0x3040..=0x30FF => Kana,
+        0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF | 0x20000..=0x3FFFF => Cjk,
+        0x3000..=0x303F | 0xFF00..=0xFFEF => Fullwidth,
+        0x2500..=0x259F => BoxDrawing,
+        0xE000..=0xF8FF | 0xF0000..=0x10FFFF => PrivateUse,
+        0x2300..=0x23FF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0x1F000..=0x1FAFF => {
+            if first.width() == Some(2) {
+                Emoji
+            } else {
+                TextEmoji
+            }
+        }
+        _ if is_ambiguous(first) => Ambiguous,
+        _ if first.width() == Some(0) => Combining,
+        _ => return None,
+    };
+    Some(category)
+}
+
+/// A single sample grapheme.
+pub struct Sample {
+    pub category: SampleCategory,
+    pub text: &'static str,
+}
+
+const fn sample(category: SampleCategory, text: &'static str) -> Sample {
+    Sample { category, text }
+}
+
+use SampleCategory::*;
+
+/// All samples, grouped by category.
+#[rustfmt::skip]
+pub const SAMPLES: &[Sample] = &[
+    sample(Ascii, "a"),
+    sample(Ascii, "~"),
+    sample(Cjk, "中"),
+    sample(Cjk, "漢"),
+    sample(Kana, "あ"),
+    sample(Kana, "カ"),
+    sample(Hangul, "한"),
+    sample(Hangul, "글"),
+    sample(Fullwidth, "Ａ"),
+    sample(Fullwidth, "。"),
+    sample(Fullwidth, "！"),
+    sample(Ambiguous, "±"),
+    sample(Ambiguous, "→"),
+    sample(Ambiguous, "○"),
+    sample(Ambiguous, "α"),
+    sample(Ambiguous, "Ж"),
+    sample(Ambiguous, "①"),
+    sample(Emoji, "😀"),
+    sample(Emoji, "🦀"),
+    sample(Emoji, "🚀"),
+    sample(TextEmoji, "❤"),
+    sample(TextEmoji, "☺"),
+    sample(EmojiVariant, "❤\u{FE0F}"),
+    sample(EmojiVariant, "☺\u{FE0F}"),
+    sample(EmojiVariant, "✔\u{FE0F}"),
+    sample(Zwj, "👩\u{200D}👩\u{200D}👧"),
+    sample(Zwj, "🧑\u{200D}💻"),
+    sample(Zwj, "👩\u{200D}❤\u{FE0F}\u{200D}💋\u{200D}👨"),
+    sample(SkinTone, "👍🏽"),
+    sample(SkinTone, "👋🏿"),
+    sample(HairColors, "👩\u{200D}🦰"),
+    sample(HairColors, "🧑\u{200D}🦳"),
+    sample(Flag, "🇯🇵"),
+    sample(Flag, "🇺🇸"),
+    sample(Flag, "🇦"),
+    sample(Keycap, "1\u{FE0F}\u{20E3}"),
+    sample(Keycap, "#\u{FE0F}\u{20E3}"),
+    sample(Combining, "e\u{0301}"),
+    sample(Combining, "n\u{0303}"),
+    sample(BoxDrawing, "─"),
+    sample(BoxDrawing, "█"),
+    sample(PrivateUse, "\u{E0B0}"),
+    sample(PrivateUse, "\u{F101}"),
+];
+
+/// Emoji bases used to build skin-tone sequences when scanning.
+const TONE_BASES: [char; 6] = ['👍', '👋', '👏', '🙌', '🧑', '👩'];
+
+/// Fitzpatrick skin-tone modifiers (U+1F3FB–U+1F3FF).
+const TONES: [char; 5] = ['🏻', '🏼', '🏽', '🏾', '🏿'];
+
+/// Flag sequences (regional indicator pairs) used when scanning.
+const FLAGS: [&str; 8] = ["CN", "JP", "KR", "US", "GB", "DE", "FR", "TW"];
+
+/// Returns true for private use code points and noncharacters, which are
+/// assigned but carry no Unicode width data of their own.
+fn is_private_or_noncharacter(c: char) -> bool {
+    let cp = c as u32;
+    matches!(cp, 0xE000..=0xF8FF | 0xFDD0..=0xFDEF | 0xF0000..=0x10FFFF) || cp & 0xFFFE == 0xFFFE
+}
+
+/// Returns every printable scalar value assigned as of Unicode 16.0 (skipping
+/// controls, surrogates and [`is_private_or_noncharacter`]), followed by common
+/// multi-code-point emoji sequences (keycaps, skin tones, flags, ZWJ).
+pub fn scan_candidates() -> Vec<String> {
+    let mut out: Vec<String> = ('\u{20}'..='\u{10FFFF}')
+        .filter(|&c| !c.is_control() && age(c).is_some() && !is_private_or_noncharacter(c))
+        .map(String::from)
+        .collect();
+
+    // Keycaps
+    for c in "0123456789#*".chars() {
+        out.push(format!("{c}\u{FE0F}\u{20E3}"));
+    }
+
+    // Skin tones
+    for base in TONE_BASES {
+        for tone in TONES {
+            out.push(format!("{base}{tone}"));
+        }
+    }
+
+    // Flags: map ASCII letters onto regional indicators
+    for code in FLAGS {
+        let flag: String = code
+            .chars()
+            .filter_map(|c| char::from_u32(0x1F1E6 + (c as u32 - 'A' as u32)))
+            .collect();
+        out.push(flag);
+    }
+
+    // Curated ZWJ and variation sequences
+    out.extend(
+        SAMPLES
+            .iter()
+            .filter(|s| s.text.chars().count() > 1)
+            .map(|s| s.text.to_string()),
+    );
+
+    out
+}
+
+/// Returns the graphemes to measure: each distinct grapheme of `input` once,
+/// in order of first appearance, or [`scan_candidates`] without input.
+pub fn collect_candidates(input: Option<&str>) -> Vec<String> {
+    match input {
+        Some(text) => {
+            let mut seen = HashSet::new();
+            text.graphemes(true)
+                .filter(|g| seen.insert(*g))
+                .map(String::from)
+                .collect()
+        }
+        None => scan_candidates(),
+    }
+}
@@ -0,0 +1,254 @@
+//! Tab stop handling based on display columns.
+//!
+//! A `\t` advances to the next tab stop measured in terminal columns, so
+//! CJK or emoji before a tab shrink it accordingly (unlike byte-based tools).
+
+use crate::commands::policy::LayoutPolicy;
+use crate::config::Context;
+use crate::error::RunefixError;
+use crate::i18n::keys::ErrorKey;
+use anyhow::{Result, bail};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Largest tab stop column accepted by `parse_tab_stops`.
+pub const MAX_TAB_STOP: usize = 1024;
+
+/// Tab stop positions in display columns.
+///
+/// A single value `N` means a stop every `N` columns. A list such as
+/// `4,8,12` sets explicit stops; past the last one, the final interval repeats.
+#[derive(Debug, Clone)]
+pub struct TabStops {
+    stops: Vec<usize>,
+}
+
+impl Default for TabStops {
+    /// A stop every 8 columns, as on a typical terminal.
+    fn default() -> Self {
+        TabStops { stops: vec![8] }
+    }
+}
+
+impl TabStops {
+    /// Returns the column of the next tab stop strictly after `col`.
+    pub fn next_stop(&self, col: usize) -> usize {
+        if let [every] = self.stops[..] {
+            return (col / every).saturating_add(1).saturating_mul(every);
+        }
+
+        if let Some(&stop) = self.stops.iter().find(|&&s| s > col) {
+            return stop;
+        }
+
+        // Past the last explicit stop: repeat the final interval
+        let last = self.stops[self.stops.len() - 1];
+        let step = last - self.stops[self.stops.len() - 2];
+        let jumps = ((col - last) / step).saturating_add(1);
+        last.saturating_add(jumps.saturating_mul(step))
+    }
+
+    /// Returns the width of a tab starting at `col`.
+    pub fn tab_width(&self, col: usize) -> usize {
+        self.next_stop(col).saturating_sub(col)
+    }
+
+    /// Returns true if `col` is a tab stop.
+    fn is_stop(&self, col: usize) -> bool {
+        col > 0 && self.next_stop(col - 1) == col
+    }
+}
+
+/// Parses a tab stop spec: `N` or an ascending comma-separated list (`4,8,12`),
+/// with no stop past `MAX_TAB_STOP`.
+pub fn parse_tab_stops(ctx: &Context, spec: &str) -> Result<TabStops> {
+    let stops: Vec<usize> = spec
+        .split(',')
+        .map(|s| s.trim().parse::<usize>())
+        .collect::<Result<_, _>>()
+        .unwrap_or_default();
+
+    let ascending = stops.windows(2).all(|w| w[0] < w[1]);
+    let bounded = stops.last().is_some_and(|&s| s <= MAX_TAB_STOP);
+    if stops.is_empty() || stops[0] == 0 || !ascending || !bounded {
+        bail!(RunefixError::new(ctx, ErrorKey::TabStopInvalid));
+    }
+
+    Ok(TabStops { stops })
+}
+
+/// Returns `(grapheme, width)` pairs, where each tab is as wide as the gap
+/// to the next stop. Columns restart after every newline.
+pub fn grapheme_widths_with_tabs<'a>(
+    text: &'a str,
+    stops: &TabStops,
+    policy: &LayoutPolicy,
+) -> Vec<(&'a str, usize)> {
+    let mut col = 0;
+
+    text.graphemes(true)
+        .map(|g| {
+            let w = match g {
+                "\t" => stops.tab_width(col),
+                "\n" | "\r\n" => {
+                    col = 0;
+                    return (g, 0);
+                }
+                _ => policy.display_width(g),
+            };
+            col += w;
+            (g, w)
+        })
+        .collect()
+}
+
+/// Same as `display_width_with_policy`, but measures tabs by column.
+pub fn display_width_with_tabs(text: &str, stops: &TabStops, policy: &LayoutPolicy) -> usize {
+    grapheme_widths_with_tabs(text, stops, policy)
+        .iter()
+        .map(|(_, w)| w)
+        .sum()
+}
+
+/// Same as `truncate_by_width_with_policy`, but measures tabs by column.
+pub fn truncate_with_tabs<'a>(
+    text: &'a str,
+    max_width: usize,
+    stops: &TabStops,
+    policy: &LayoutPolicy,
+) -> &'a str {
+    let mut total = 0;
+    let mut end = 0;
+
+    for (g, w) in grapheme_widths_with_tabs(text, stops, policy) {
+        if total + w > max_width {
+            break;
+        }
+        total += w;
+        end += g.len();
+    }
+
+    &text[..end]
+}
+
+/// Same as `split_by_width_with_policy`, but measures tabs by column.
+///
+/// Tab widths are computed relative to the start of each output line.
+pub fn split_with_tabs(
+    text: &str,
+    max_width: usize,
+    stops: &TabStops,
+    policy: &LayoutPolicy,
+) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut line = String::new();
+    let mut width = 0;
+
+    for g in text.graphemes(true) {
+        let mut w = match g {
+            "\t" => stops.tab_width(width),
+            _ => policy.display_width(g),
+        };
+
+        if width + w > max_width && !line.is_empty() {
+            result.push(std::mem::take(&mut line));
+            width = 0;
+
+            // A tab at the start of a new line spans a full first stop
+            if g == "\t" {
+                w = stops.tab_width(0);
+            }
+        }
+
+        line.push_str(g);
+        width += w;
+    }
+
+    if !line.is_empty() {
+        result.push(line);
+    }
+
+    result
+}
+
+/// Replaces tabs in a single line with spaces up to the next stop.
+///
+/// With `initial`, only tabs before the first non-blank are expanded.
+pub fn expand_line(line: &str, stops: &TabStops, policy: &LayoutPolicy, initial: bool) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut col = 0;
+    let mut leading = true;
+
+    for g in line.graphemes(true) {
+        if g == "\t" {
+            let w = stops.tab_width(col);
+            if leading || !initial {
+                out.push_str(&" ".repeat(w));
+            } else {
+                out.push('\t');
+            }
+            col += w;
+            continue;
+        }
+
+        if g != " " {
+            leading = false;
+        }
+        out.push_str(g);
+        col += policy.display_width(g);
+    }
+
+    out
+}
+
+/// Replaces runs of blanks that end on a tab stop with tabs.
+///
+/// By default only leading blanks are converted; with `all`, any run of two
+/// or more blanks ending on a stop is converted as well.
+pub fn unexpand_line(line: &str, stops: &TabStops, policy: &LayoutPolicy, all: bool) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut col = 0;
+    let mut pending = 0; // Buffered blank columns not yet written
+    let mut pending_tab = false; // Whether the buffered run contains a tab
+    let mut converting = true;
+
+    for g in line.graphemes(true) {
+        if converting && (g == " " || g == "\t") {
+            let next = if g == "\t" {
+                pending_tab = true;
+                stops.next_stop(col)
+            } else {
+                col + 1
+            };
+            pending += next - col;
+            col = next;
+
+            // Run ends on a stop: emit a tab (a lone space stays a space)
+            if stops.is_stop(col) {
+                if pending >= 2 || pending_tab {
+                    out.push('\t');
+                } else {
+                    out.push_str(&" ".repeat(pending));
+                }
+                pending = 0;
+                pending_tab = false;
+            }
+            continue;
+        }
+
+        // Flush blanks that did not reach a stop
+        out.push_str(&" ".repeat(pending));
+        pending = 0;
+
+        if !all {
+            converting = false;
+        }
+        out.push_str(g);
+        col += match g {
+            "\t" => stops.tab_width(col),
+            _ => policy.display_width(g),
+        };
+    }
+
+    out.push_str(&" ".repeat(pending));
+    out
+}
@@ -0,0 +1,510 @@
+//! Unicode version in which each code point was assigned.
+//!
+//! Terminals and libraries often ship width tables several Unicode versions
+//! behind `runefix-core`, drawing newer code points as one-column "tofu".
+//! Selecting a [`UnicodeVersion`] lets policies reproduce that behavior.
+
+use runefix_core::UNICODE_VERSION;
+use std::fmt;
+
+/// A Unicode version (`major.minor`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnicodeVersion {
+    pub major: u8,
+    pub minor: u8,
+}
+
+impl UnicodeVersion {
+    /// The version of the tables in `runefix-core`.
+    pub fn latest() -> Self {
+        let (major, minor, _) = UNICODE_VERSION;
+        Self { major, minor }
+    }
+
+    /// Parses `15`, `15.1` or `15.1.0` (the update number is ignored).
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.trim().split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().map_or(Some(0), |m| m.parse().ok())?;
+        Some(Self { major, minor })
+    }
+}
+
+impl fmt::Display for UnicodeVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Parses a `--unicode-version` value, accepting 1.1 up to the core version.
+pub fn parse_unicode_version(s: &str) -> Result<UnicodeVersion, String> {
+    let latest = UnicodeVersion::latest();
+    match UnicodeVersion::parse(s) {
+        Some(v) if v >= UnicodeVersion { major: 1, minor: 1 } && v <= latest => Ok(v),
+        Some(_) => Err(format!("supported versions are 1.1 to {latest}")),
+        None => Err("expected a version such as 13.0 or 15.1".to_string()),
+    }
+}
+
+/// Returns the version in which `c` was assigned, or `None` if it is still
+/// unassigned (as of Unicode 16.0).
+pub fn age(c: char) -> Option<UnicodeVersion> {
+    let cp = c as u32;
+    match AGES.binary_search_by(|&(start, end, _, _)| {
+        if end < cp {
+            std::cmp::Ordering::Less
+        } else if start > cp {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    }) {
+        Ok(i) => Some(UnicodeVersion {
+            major: AGES[i].2,
+            minor: AGES[i].3,
+        }),
+        Err(_) => None,
+    }
+}
+
+/// Assigned code point ranges and their age (`first, last, major, minor`),
+/// from `DerivedAge.txt` of Unicode 16.0.
+#[rustfmt::skip]
+const AGES: &[(u32, u32, u8, u8)] = &[
+    (0x00000, 0x001F5, 1, 1), (0x001F6, 0x001F9, 3, 0), (0x001FA, 0x00217, 1, 1), (0x00218, 0x0021F, 3, 0),
+    (0x00220, 0x00220, 3, 2), (0x00221, 0x00221, 4, 0), (0x00222, 0x00233, 3, 0), (0x00234, 0x00236, 4, 0),
+    (0x00237, 0x00241, 4, 1), (0x00242, 0x0024F, 5, 0), (0x00250, 0x002A8, 1, 1), (0x002A9, 0x002AD, 3, 0),
+    (0x002AE, 0x002AF, 4, 0), (0x002B0, 0x002DE, 1, 1), (0x002DF, 0x002DF, 3, 0), (0x002E0, 0x002E9, 1, 1),
+    (0x002EA, 0x002EE, 3, 0), (0x002EF, 0x002FF, 4, 0), (0x00300, 0x00345, 1, 1), (0x00346, 0x0034E, 3, 0),
+    (0x0034F, 0x0034F, 3, 2), (0x00350, 0x00357, 4, 0), (0x00358, 0x0035C, 4, 1), (0x0035D, 0x0035F, 4, 0),
+    (0x00360, 0x00361, 1, 1), (0x00362, 0x00362, 3, 0), (0x00363, 0x0036F, 3, 2), (0x00370, 0x00373, 5, 1),
+    (0x00374, 0x00375, 1, 1), (0x00376, 0x00377, 5, 1), (0x0037A, 0x0037A, 1, 1), (0x0037B, 0x0037D, 5, 0),
+    (0x0037E, 0x0037E, 1, 1), (0x0037F, 0x0037F, 7, 0), (0x00384, 0x0038A, 1, 1), (0x0038C, 0x0038C, 1, 1),
+    (0x0038E, 0x003A1, 1, 1), (0x003A3, 0x003CE, 1, 1), (0x003CF, 0x003CF, 5, 1), (0x003D0, 0x003D6, 1, 1),
+    (0x003D7, 0x003D7, 3, 0), (0x003D8, 0x003D9, 3, 2), (0x003DA, 0x003DA, 1, 1), (0x003DB, 0x003DB, 3, 0),
+    (0x003DC, 0x003DC, 1, 1), (0x003DD, 0x003DD, 3, 0), (0x003DE, 0x003DE, 1, 1), (0x003DF, 0x003DF, 3, 0),
+    (0x003E0, 0x003E0, 1, 1), (0x003E1, 0x003E1, 3, 0), (0x003E2, 0x003F3, 1, 1), (0x003F4, 0x003F5, 3, 1),
+    (0x003F6, 0x003F6, 3, 2), (0x003F7, 0x003FB, 4, 0), (0x003FC, 0x003FF, 4, 1), (0x00400, 0x00400, 3, 0),
+    (0x00401, 0x0040C, 1, 1), (0x0040D, 0x0040D, 3, 0), (0x0040E, 0x0044F, 1, 1), (0x00450, 0x00450, 3, 0),
+    (0x00451, 0x0045C, 1, 1), (0x0045D, 0x0045D, 3, 0), (0x0045E, 0x00486, 1, 1), (0x00487, 0x00487, 5, 1),
+    (0x00488, 0x00489, 3, 0), (0x0048A, 0x0048B, 3, 2), (0x0048C, 0x0048F, 3, 0), (0x00490, 0x004C4, 1, 1),
+    (0x004C5, 0x004C6, 3, 2), (0x004C7, 0x004C8, 1, 1), (0x004C9, 0x004CA, 3, 2), (0x004CB, 0x004CC, 1, 1),
+    (0x004CD, 0x004CE, 3, 2), (0x004CF, 0x004CF, 5, 0), (0x004D0, 0x004EB, 1, 1), (0x004EC, 0x004ED, 3, 0),
+    (0x004EE, 0x004F5, 1, 1), (0x004F6, 0x004F7, 4, 1), (0x004F8, 0x004F9, 1, 1), (0x004FA, 0x004FF, 5, 0),
+    (0x00500, 0x0050F, 3, 2), (0x00510, 0x00513, 5, 0), (0x00514, 0x00523, 5, 1), (0x00524, 0x00525, 5, 2),
+    (0x00526, 0x00527, 6, 0), (0x00528, 0x0052F, 7, 0), (0x00531, 0x00556, 1, 1), (0x00559, 0x0055F, 1, 1),
+    (0x00560, 0x00560, 11, 0), (0x00561, 0x00587, 1, 1), (0x00588, 0x00588, 11, 0), (0x00589, 0x00589, 1, 1),
+    (0x0058A, 0x0058A, 3, 0), (0x0058D, 0x0058E, 7, 0), (0x0058F, 0x0058F, 6, 1), (0x00591, 0x005A1, 2, 0),
+    (0x005A2, 0x005A2, 4, 1), (0x005A3, 0x005AF, 2, 0), (0x005B0, 0x005B9, 1, 1), (0x005BA, 0x005BA, 5, 0),
+    (0x005BB, 0x005C3, 1, 1), (0x005C4, 0x005C4, 2, 0), (0x005C5, 0x005C7, 4, 1), (0x005D0, 0x005EA, 1, 1),
+    (0x005EF, 0x005EF, 11, 0), (0x005F0, 0x005F4, 1, 1), (0x00600, 0x00603, 4, 0), (0x00604, 0x00604, 6, 1),
+    (0x00605, 0x00605, 7, 0), (0x00606, 0x0060A, 5, 1), (0x0060B, 0x0060B, 4, 1), (0x0060C, 0x0060C, 1, 1),
+    (0x0060D, 0x00615, 4, 0), (0x00616, 0x0061A, 5, 1), (0x0061B, 0x0061B, 1, 1), (0x0061C, 0x0061C, 6, 3),
+    (0x0061D, 0x0061D, 14, 0), (0x0061E, 0x0061E, 4, 1), (0x0061F, 0x0061F, 1, 1), (0x00620, 0x00620, 6, 0),
+    (0x00621, 0x0063A, 1, 1), (0x0063B, 0x0063F, 5, 1), (0x00640, 0x00652, 1, 1), (0x00653, 0x00655, 3, 0),
+    (0x00656, 0x00658, 4, 0), (0x00659, 0x0065E, 4, 1), (0x0065F, 0x0065F, 6, 0), (0x00660, 0x0066D, 1, 1),
+    (0x0066E, 0x0066F, 3, 2), (0x00670, 0x006B7, 1, 1), (0x006B8, 0x006B9, 3, 0), (0x006BA, 0x006BE, 1, 1),
+    (0x006BF, 0x006BF, 3, 0), (0x006C0, 0x006CE, 1, 1), (0x006CF, 0x006CF, 3, 0), (0x006D0, 0x006ED, 1, 1),
+    (0x006EE, 0x006EF, 4, 0), (0x006F0, 0x006F9, 1, 1), (0x006FA, 0x006FE, 3, 0), (0x006FF, 0x006FF, 4, 0),
+    (0x00700, 0x0070D, 3, 0), (0x0070F, 0x0072C, 3, 0), (0x0072D, 0x0072F, 4, 0), (0x00730, 0x0074A, 3, 0),
+    (0x0074D, 0x0074F, 4, 0), (0x00750, 0x0076D, 4, 1), (0x0076E, 0x0077F, 5, 1), (0x00780, 0x007B0, 3, 0),
+    (0x007B1, 0x007B1, 3, 2), (0x007C0, 0x007FA, 5, 0), (0x007FD, 0x007FF, 11, 0), (0x00800, 0x0082D, 5, 2),
+    (0x00830, 0x0083E, 5, 2), (0x00840, 0x0085B, 6, 0), (0x0085E, 0x0085E, 6, 0), (0x00860, 0x0086A, 10, 0),
+    (0x00870, 0x0088E, 14, 0), (0x00890, 0x00891, 14, 0), (0x00897, 0x00897, 16, 0), (0x00898, 0x0089F, 14, 0),
+    (0x008A0, 0x008A0, 6, 1), (0x008A1, 0x008A1, 7, 0), (0x008A2, 0x008AC, 6, 1), (0x008AD, 0x008B2, 7, 0),
+    (0x008B3, 0x008B4, 8, 0), (0x008B5, 0x008B5, 14, 0), (0x008B6, 0x008BD, 9, 0), (0x008BE, 0x008C7, 13, 0),
+    (0x008C8, 0x008D2, 14, 0), (0x008D3, 0x008D3, 11, 0), (0x008D4, 0x008E2, 9, 0), (0x008E3, 0x008E3, 8, 0),
+    (0x008E4, 0x008FE, 6, 1), (0x008FF, 0x008FF, 7, 0), (0x00900, 0x00900, 5, 2), (0x00901, 0x00903, 1, 1),
+    (0x00904, 0x00904, 4, 0), (0x00905, 0x00939, 1, 1), (0x0093A, 0x0093B, 6, 0), (0x0093C, 0x0094D, 1, 1),
+    (0x0094E, 0x0094E, 5, 2), (0x0094F, 0x0094F, 6, 0), (0x00950, 0x00954, 1, 1), (0x00955, 0x00955, 5, 2),
+    (0x00956, 0x00957, 6, 0), (0x00958, 0x00970, 1, 1), (0x00971, 0x00972, 5, 1), (0x00973, 0x00977, 6, 0),
+    (0x00978, 0x00978, 7, 0), (0x00979, 0x0097A, 5, 2), (0x0097B, 0x0097C, 5, 0), (0x0097D, 0x0097D, 4, 1),
+    (0x0097E, 0x0097F, 5, 0), (0x00980, 0x00980, 7, 0), (0x00981, 0x00983, 1, 1), (0x00985, 0x0098C, 1, 1),
+    (0x0098F, 0x00990, 1, 1), (0x00993, 0x009A8, 1, 1), (0x009AA, 0x009B0, 1, 1), (0x009B2, 0x009B2, 1, 1),
+    (0x009B6, 0x009B9, 1, 1), (0x009BC, 0x009BC, 1, 1), (0x009BD, 0x009BD, 4, 0), (0x009BE, 0x009C4, 1, 1),
+    (0x009C7, 0x009C8, 1, 1), (0x009CB, 0x009CD, 1, 1), (0x009CE, 0x009CE, 4, 1), (0x009D7, 0x009D7, 1, 1),
+    (0x009DC, 0x009DD, 1, 1), (0x009DF, 0x009E3, 1, 1), (0x009E6, 0x009FA, 1, 1), (0x009FB, 0x009FB, 5, 2),
+    (0x009FC, 0x009FD, 10, 0), (0x009FE, 0x009FE, 11, 0), (0x00A01, 0x00A01, 4, 0), (0x00A02, 0x00A02, 1, 1),
+    (0x00A03, 0x00A03, 4, 0), (0x00A05, 0x00A0A, 1, 1), (0x00A0F, 0x00A10, 1, 1), (0x00A13, 0x00A28, 1, 1),
+    (0x00A2A, 0x00A30, 1, 1), (0x00A32, 0x00A33, 1, 1), (0x00A35, 0x00A36, 1, 1), (0x00A38, 0x00A39, 1, 1),
+    (0x00A3C, 0x00A3C, 1, 1), (0x00A3E, 0x00A42, 1, 1), (0x00A47, 0x00A48, 1, 1), (0x00A4B, 0x00A4D, 1, 1),
+    (0x00A51, 0x00A51, 5, 1), (0x00A59, 0x00A5C, 1, 1), (0x00A5E, 0x00A5E, 1, 1), (0x00A66, 0x00A74, 1, 1),
+    (0x00A75, 0x00A75, 5, 1), (0x00A76, 0x00A76, 11, 0), (0x00A81, 0x00A83, 1, 1), (0x00A85, 0x00A8B, 1, 1),
+    (0x00A8C, 0x00A8C, 4, 0), (0x00A8D, 0x00A8D, 1, 1), (0x00A8F, 0x00A91, 1, 1), (0x00A93, 0x00AA8, 1, 1),
+    (0x00AAA, 0x00AB0, 1, 1), (0x00AB2, 0x00AB3, 1, 1), (0x00AB5, 0x00AB9, 1, 1), (0x00ABC, 0x00AC5, 1, 1),
+    (0x00AC7, 0x00AC9, 1, 1), (0x00ACB, 0x00ACD, 1, 1), (0x00AD0, 0x00AD0, 1, 1), (0x00AE0, 0x00AE0, 1, 1),
+    (0x00AE1, 0x00AE3, 4, 0), (0x00AE6, 0x00AEF, 1, 1), (0x00AF0, 0x00AF0, 6, 1), (0x00AF1, 0x00AF1, 4, 0),
+    (0x00AF9, 0x00AF9, 8, 0), (0x00AFA, 0x00AFF, 10, 0), (0x00B01, 0x00B03, 1, 1), (0x00B05, 0x00B0C, 1, 1),
+    (0x00B0F, 0x00B10, 1, 1), (0x00B13, 0x00B28, 1, 1), (0x00B2A, 0x00B30, 1, 1), (0x00B32, 0x00B33, 1, 1),
+    (0x00B35, 0x00B35, 4, 0), (0x00B36, 0x00B39, 1, 1), (0x00B3C, 0x00B43, 1, 1), (0x00B44, 0x00B44, 5, 1),
+    (0x00B47, 0x00B48, 1, 1), (0x00B4B, 0x00B4D, 1, 1), (0x00B55, 0x00B55, 13, 0), (0x00B56, 0x00B57, 1, 1),
+    (0x00B5C, 0x00B5D, 1, 1), (0x00B5F, 0x00B61, 1, 1), (0x00B62, 0x00B63, 5, 1), (0x00B66, 0x00B70, 1, 1),
+    (0x00B71, 0x00B71, 4, 0), (0x00B72, 0x00B77, 6, 0), (0x00B82, 0x00B83, 1, 1), (0x00B85, 0x00B8A, 1, 1),
+    (0x00B8E, 0x00B90, 1, 1), (0x00B92, 0x00B95, 1, 1), (0x00B99, 0x00B9A, 1, 1), (0x00B9C, 0x00B9C, 1, 1),
+    (0x00B9E, 0x00B9F, 1, 1), (0x00BA3, 0x00BA4, 1, 1), (0x00BA8, 0x00BAA, 1, 1), (0x00BAE, 0x00BB5, 1, 1),
+    (0x00BB6, 0x00BB6, 4, 1), (0x00BB7, 0x00BB9, 1, 1), (0x00BBE, 0x00BC2, 1, 1), (0x00BC6, 0x00BC8, 1, 1),
+    (0x00BCA, 0x00BCD, 1, 1), (0x00BD0, 0x00BD0, 5, 1), (0x00BD7, 0x00BD7, 1, 1), (0x00BE6, 0x00BE6, 4, 1),
+    (0x00BE7, 0x00BF2, 1, 1), (0x00BF3, 0x00BFA, 4, 0), (0x00C00, 0x00C00, 7, 0), (0x00C01, 0x00C03, 1, 1),
+    (0x00C04, 0x00C04, 11, 0), (0x00C05, 0x00C0C, 1, 1), (0x00C0E, 0x00C10, 1, 1), (0x00C12, 0x00C28, 1, 1),
+    (0x00C2A, 0x00C33, 1, 1), (0x00C34, 0x00C34, 7, 0), (0x00C35, 0x00C39, 1, 1), (0x00C3C, 0x00C3C, 14, 0),
+    (0x00C3D, 0x00C3D, 5, 1), (0x00C3E, 0x00C44, 1, 1), (0x00C46, 0x00C48, 1, 1), (0x00C4A, 0x00C4D, 1, 1),
+    (0x00C55, 0x00C56, 1, 1), (0x00C58, 0x00C59, 5, 1), (0x00C5A, 0x00C5A, 8, 0), (0x00C5D, 0x00C5D, 14, 0),
+    (0x00C60, 0x00C61, 1, 1), (0x00C62, 0x00C63, 5, 1), (0x00C66, 0x00C6F, 1, 1), (0x00C77, 0x00C77, 12, 0),
+    (0x00C78, 0x00C7F, 5, 1), (0x00C80, 0x00C80, 9, 0), (0x00C81, 0x00C81, 7, 0), (0x00C82, 0x00C83, 1, 1),
+    (0x00C84, 0x00C84, 11, 0), (0x00C85, 0x00C8C, 1, 1), (0x00C8E, 0x00C90, 1, 1), (0x00C92, 0x00CA8, 1, 1),
+    (0x00CAA, 0x00CB3, 1, 1), (0x00CB5, 0x00CB9, 1, 1), (0x00CBC, 0x00CBD, 4, 0), (0x00CBE, 0x00CC4, 1, 1),
+    (0x00CC6, 0x00CC8, 1, 1), (0x00CCA, 0x00CCD, 1, 1), (0x00CD5, 0x00CD6, 1, 1), (0x00CDD, 0x00CDD, 14, 0),
+    (0x00CDE, 0x00CDE, 1, 1), (0x00CE0, 0x00CE1, 1, 1), (0x00CE2, 0x00CE3, 5, 0), (0x00CE6, 0x00CEF, 1, 1),
+    (0x00CF1, 0x00CF2, 5, 0), (0x00CF3, 0x00CF3, 15, 0), (0x00D00, 0x00D00, 10, 0), (0x00D01, 0x00D01, 7, 0),
+    (0x00D02, 0x00D03, 1, 1), (0x00D04, 0x00D04, 13, 0), (0x00D05, 0x00D0C, 1, 1), (0x00D0E, 0x00D10, 1, 1),
+    (0x00D12, 0x00D28, 1, 1), (0x00D29, 0x00D29, 6, 0), (0x00D2A, 0x00D39, 1, 1), (0x00D3A, 0x00D3A, 6, 0),
+    (0x00D3B, 0x00D3C, 10, 0), (0x00D3D, 0x00D3D, 5, 1), (0x00D3E, 0x00D43, 1, 1), (0x00D44, 0x00D44, 5, 1),
+    (0x00D46, 0x00D48, 1, 1), (0x00D4A, 0x00D4D, 1, 1), (0x00D4E, 0x00D4E, 6, 0), (0x00D4F, 0x00D4F, 9, 0),
+    (0x00D54, 0x00D56, 9, 0), (0x00D57, 0x00D57, 1, 1), (0x00D58, 0x00D5E, 9, 0), (0x00D5F, 0x00D5F, 8, 0),
+    (0x00D60, 0x00D61, 1, 1), (0x00D62, 0x00D63, 5, 1), (0x00D66, 0x00D6F, 1, 1), (0x00D70, 0x00D75, 5, 1),
+    (0x00D76, 0x00D78, 9, 0), (0x00D79, 0x00D7F, 5, 1), (0x00D81, 0x00D81, 13, 0), (0x00D82, 0x00D83, 3, 0),
+    (0x00D85, 0x00D96, 3, 0), (0x00D9A, 0x00DB1, 3, 0), (0x00DB3, 0x00DBB, 3, 0), (0x00DBD, 0x00DBD, 3, 0),
+    (0x00DC0, 0x00DC6, 3, 0), (0x00DCA, 0x00DCA, 3, 0), (0x00DCF, 0x00DD4, 3, 0), (0x00DD6, 0x00DD6, 3, 0),
+    (0x00DD8, 0x00DDF, 3, 0), (0x00DE6, 0x00DEF, 7, 0), (0x00DF2, 0x00DF4, 3, 0), (0x00E01, 0x00E3A, 1, 1),
+    (0x00E3F, 0x00E5B, 1, 1), (0x00E81, 0x00E82, 1, 1), (0x00E84, 0x00E84, 1, 1), (0x00E86, 0x00E86, 12, 0),
+    (0x00E87, 0x00E88, 1, 1), (0x00E89, 0x00E89, 12, 0), (0x00E8A, 0x00E8A, 1, 1), (0x00E8C, 0x00E8C, 12, 0),
+    (0x00E8D, 0x00E8D, 1, 1), (0x00E8E, 0x00E93, 12, 0), (0x00E94, 0x00E97, 1, 1), (0x00E98, 0x00E98, 12, 0),
+    (0x00E99, 0x00E9F, 1, 1), (0x00EA0, 0x00EA0, 12, 0), (0x00EA1, 0x00EA3, 1, 1), (0x00EA5, 0x00EA5, 1, 1),
+    (0x00EA7, 0x00EA7, 1, 1), (0x00EA8, 0x00EA9, 12, 0), (0x00EAA, 0x00EAB, 1, 1), (0x00EAC, 0x00EAC, 12, 0),
+    (0x00EAD, 0x00EB9, 1, 1), (0x00EBA, 0x00EBA, 12, 0), (0x00EBB, 0x00EBD, 1, 1), (0x00EC0, 0x00EC4, 1, 1),
+    (0x00EC6, 0x00EC6, 1, 1), (0x00EC8, 0x00ECD, 1, 1), (0x00ECE, 0x00ECE, 15, 0), (0x00ED0, 0x00ED9, 1, 1),
+    (0x00EDC, 0x00EDD, 1, 1), (0x00EDE, 0x00EDF, 6, 1), (0x00F00, 0x00F47, 2, 0), (0x00F49, 0x00F69, 2, 0),
+    (0x00F6A, 0x00F6A, 3, 0), (0x00F6B, 0x00F6C, 5, 1), (0x00F71, 0x00F8B, 2, 0), (0x00F8C, 0x00F8F, 6, 0),
+    (0x00F90, 0x00F95, 2, 0), (0x00F96, 0x00F96, 3, 0), (0x00F97, 0x00F97, 2, 0), (0x00F99, 0x00FAD, 2, 0),
+    (0x00FAE, 0x00FB0, 3, 0), (0x00FB1, 0x00FB7, 2, 0), (0x00FB8, 0x00FB8, 3, 0), (0x00FB9, 0x00FB9, 2, 0),
+    (0x00FBA, 0x00FBC, 3, 0), (0x00FBE, 0x00FCC, 3, 0), (0x00FCE, 0x00FCE, 5, 1), (0x00FCF, 0x00FCF, 3, 0),
+    (0x00FD0, 0x00FD1, 4, 1), (0x00FD2, 0x00FD4, 5, 1), (0x00FD5, 0x00FD8, 5, 2), (0x00FD9, 0x00FDA, 6, 0),
+    (0x01000, 0x01021, 3, 0), (0x01022, 0x01022, 5, 1), (0x01023, 0x01027, 3, 0), (0x01028, 0x01028, 5, 1),
+    (0x01029, 0x0102A, 3, 0), (0x0102B, 0x0102B, 5, 1), (0x0102C, 0x01032, 3, 0), (0x01033, 0x01035, 5, 1),
+    (0x01036, 0x01039, 3, 0), (0x0103A, 0x0103F, 5, 1), (0x01040, 0x01059, 3, 0), (0x0105A, 0x01099, 5, 1),
+    (0x0109A, 0x0109D, 5, 2), (0x0109E, 0x0109F, 5, 1), (0x010A0, 0x010C5, 1, 1), (0x010C7, 0x010C7, 6, 1),
+    (0x010CD, 0x010CD, 6, 1), (0x010D0, 0x010F6, 1, 1), (0x010F7, 0x010F8, 3, 2), (0x010F9, 0x010FA, 4, 1),
+    (0x010FB, 0x010FB, 1, 1), (0x010FC, 0x010FC, 4, 1), (0x010FD, 0x010FF, 6, 1), (0x01100, 0x01159, 1, 1),
+    (0x0115A, 0x0115E, 5, 2), (0x0115F, 0x011A2, 1, 1), (0x011A3, 0x011A7, 5, 2), (0x011A8, 0x011F9, 1, 1),
+    (0x011FA, 0x011FF, 5, 2), (0x01200, 0x01206, 3, 0), (0x01207, 0x01207, 4, 1), (0x01208, 0x01246, 3, 0),
+    (0x01247, 0x01247, 4, 1), (0x01248, 0x01248, 3, 0), (0x0124A, 0x0124D, 3, 0), (0x01250, 0x01256, 3, 0),
+    (0x01258, 0x01258, 3, 0), (0x0125A, 0x0125D, 3, 0), (0x01260, 0x01286, 3, 0), (0x01287, 0x01287, 4, 1),
+    (0x01288, 0x01288, 3, 0), (0x0128A, 0x0128D, 3, 0), (0x01290, 0x012AE, 3, 0), (0x012AF, 0x012AF, 4, 1),
+    (0x012B0, 0x012B0, 3, 0), (0x012B2, 0x012B5, 3, 0), (0x012B8, 0x012BE, 3, 0), (0x012C0, 0x012C0, 3, 0),
+    (0x012C2, 0x012C5, 3, 0), (0x012C8, 0x012CE, 3, 0), (0x012CF, 0x012CF, 4, 1), (0x012D0, 0x012D6, 3, 0),
+    (0x012D8, 0x012EE, 3, 0), (0x012EF, 0x012EF, 4, 1), (0x012F0, 0x0130E, 3, 0), (0x0130F, 0x0130F, 4, 1),
+    (0x01310, 0x01310, 3, 0), (0x01312, 0x01315, 3, 0), (0x01318, 0x0131E, 3, 0), (0x0131F, 0x0131F, 4, 1),
+    (0x01320, 0x01346, 3, 0), (0x01347, 0x01347, 4, 1), (0x01348, 0x0135A, 3, 0), (0x0135D, 0x0135E, 6, 0),
+    (0x0135F, 0x01360, 4, 1), (0x01361, 0x0137C, 3, 0), (0x01380, 0x01399, 4, 1), (0x013A0, 0x013F4, 3, 0),
+    (0x013F5, 0x013F5, 8, 0), (0x013F8, 0x013FD, 8, 0), (0x01400, 0x01400, 5, 2), (0x01401, 0x01676, 3, 0),
+    (0x01677, 0x0167F, 5, 2), (0x01680, 0x0169C, 3, 0), (0x016A0, 0x016F0, 3, 0), (0x016F1, 0x016F8, 7, 0),
+    (0x01700, 0x0170C, 3, 2), (0x0170D, 0x0170D, 14, 0), (0x0170E, 0x01714, 3, 2), (0x01715, 0x01715, 14, 0),
+    (0x0171F, 0x0171F, 14, 0), (0x01720, 0x01736, 3, 2), (0x01740, 0x01753, 3, 2), (0x01760, 0x0176C, 3, 2),
+    (0x0176E, 0x01770, 3, 2), (0x01772, 0x01773, 3, 2), (0x01780, 0x017DC, 3, 0), (0x017DD, 0x017DD, 4, 0),
+    (0x017E0, 0x017E9, 3, 0), (0x017F0, 0x017F9, 4, 0), (0x01800, 0x0180E, 3, 0), (0x0180F, 0x0180F, 14, 0),
+    (0x01810, 0x01819, 3, 0), (0x01820, 0x01877, 3, 0), (0x01878, 0x01878, 11, 0), (0x01880, 0x018A9, 3, 0),
+    (0x018AA, 0x018AA, 5, 1), (0x018B0, 0x018F5, 5, 2), (0x01900, 0x0191C, 4, 0), (0x0191D, 0x0191E, 7, 0),
+    (0x01920, 0x0192B, 4, 0), (0x01930, 0x0193B, 4, 0), (0x01940, 0x01940, 4, 0), (0x01944, 0x0196D, 4, 0),
+    (0x01970, 0x01974, 4, 0), (0x01980, 0x019A9, 4, 1), (0x019AA, 0x019AB, 5, 2), (0x019B0, 0x019C9, 4, 1),
+    (0x019D0, 0x019D9, 4, 1), (0x019DA, 0x019DA, 5, 2), (0x019DE, 0x019DF, 4, 1), (0x019E0, 0x019FF, 4, 0),
+    (0x01A00, 0x01A1B, 4, 1), (0x01A1E, 0x01A1F, 4, 1), (0x01A20, 0x01A5E, 5, 2), (0x01A60, 0x01A7C, 5, 2),
+    (0x01A7F, 0x01A89, 5, 2), (0x01A90, 0x01A99, 5, 2), (0x01AA0, 0x01AAD, 5, 2), (0x01AB0, 0x01ABE, 7, 0),
+    (0x01ABF, 0x01AC0, 13, 0), (0x01AC1, 0x01ACE, 14, 0), (0x01B00, 0x01B4B, 5, 0), (0x01B4C, 0x01B4C, 14, 0),
+    (0x01B4E, 0x01B4F, 16, 0), (0x01B50, 0x01B7C, 5, 0), (0x01B7D, 0x01B7E, 14, 0), (0x01B7F, 0x01B7F, 16, 0),
+    (0x01B80, 0x01BAA, 5, 1), (0x01BAB, 0x01BAD, 6, 1), (0x01BAE, 0x01BB9, 5, 1), (0x01BBA, 0x01BBF, 6, 1),
+    (0x01BC0, 0x01BF3, 6, 0), (0x01BFC, 0x01BFF, 6, 0), (0x01C00, 0x01C37, 5, 1), (0x01C3B, 0x01C49, 5, 1),
+    (0x01C4D, 0x01C7F, 5, 1), (0x01C80, 0x01C88, 9, 0), (0x01C89, 0x01C8A, 16, 0), (0x01C90, 0x01CBA, 11, 0),
+    (0x01CBD, 0x01CBF, 11, 0), (0x01CC0, 0x01CC7, 6, 1), (0x01CD0, 0x01CF2, 5, 2), (0x01CF3, 0x01CF6, 6, 1),
+    (0x01CF7, 0x01CF7, 10, 0), (0x01CF8, 0x01CF9, 7, 0), (0x01CFA, 0x01CFA, 12, 0), (0x01D00, 0x01D6B, 4, 0),
+    (0x01D6C, 0x01DC3, 4, 1), (0x01DC4, 0x01DCA, 5, 0), (0x01DCB, 0x01DE6, 5, 1), (0x01DE7, 0x01DF5, 7, 0),
+    (0x01DF6, 0x01DF9, 10, 0), (0x01DFA, 0x01DFA, 14, 0), (0x01DFB, 0x01DFB, 9, 0), (0x01DFC, 0x01DFC, 6, 0),
+    (0x01DFD, 0x01DFD, 5, 2), (0x01DFE, 0x01DFF, 5, 0), (0x01E00, 0x01E9A, 1, 1), (0x01E9B, 0x01E9B, 2, 0),
+    (0x01E9C, 0x01E9F, 5, 1), (0x01EA0, 0x01EF9, 1, 1), (0x01EFA, 0x01EFF, 5, 1), (0x01F00, 0x01F15, 1, 1),
+    (0x01F18, 0x01F1D, 1, 1), (0x01F20, 0x01F45, 1, 1), (0x01F48, 0x01F4D, 1, 1), (0x01F50, 0x01F57, 1, 1),
+    (0x01F59, 0x01F59, 1, 1), (0x01F5B, 0x01F5B, 1, 1), (0x01F5D, 0x01F5D, 1, 1), (0x01F5F, 0x01F7D, 1, 1),
+    (0x01F80, 0x01FB4, 1, 1), (0x01FB6, 0x01FC4, 1, 1), (0x01FC6, 0x01FD3, 1, 1), (0x01FD6, 0x01FDB, 1, 1),
+    (0x01FDD, 0x01FEF, 1, 1), (0x01FF2, 0x01FF4, 1, 1), (0x01FF6, 0x01FFE, 1, 1), (0x02000, 0x0202E, 1, 1),
+    (0x0202F, 0x0202F, 3, 0), (0x02030, 0x02046, 1, 1), (0x02047, 0x02047, 3, 2), (0x02048, 0x0204D, 3, 0),
+    (0x0204E, 0x02052, 3, 2), (0x02053, 0x02054, 4, 0), (0x02055, 0x02056, 4, 1), (0x02057, 0x02057, 3, 2),
+    (0x02058, 0x0205E, 4, 1), (0x0205F, 0x02063, 3, 2), (0x02064, 0x02064, 5, 1), (0x02066, 0x02069, 6, 3),
+    (0x0206A, 0x02070, 1, 1), (0x02071, 0x02071, 3, 2), (0x02074, 0x0208E, 1, 1), (0x02090, 0x02094, 4, 1),
+    (0x02095, 0x0209C, 6, 0), (0x020A0, 0x020AA, 1, 1), (0x020AB, 0x020AB, 2, 0), (0x020AC, 0x020AC, 2, 1),
+    (0x020AD, 0x020AF, 3, 0), (0x020B0, 0x020B1, 3, 2), (0x020B2, 0x020B5, 4, 1), (0x020B6, 0x020B8, 5, 2),
+    (0x020B9, 0x020B9, 6, 0), (0x020BA, 0x020BA, 6, 2), (0x020BB, 0x020BD, 7, 0), (0x020BE, 0x020BE, 8, 0),
+    (0x020BF, 0x020BF, 10, 0), (0x020C0, 0x020C0, 14, 0), (0x020D0, 0x020E1, 1, 1), (0x020E2, 0x020E3, 3, 0),
+    (0x020E4, 0x020EA, 3, 2), (0x020EB, 0x020EB, 4, 1), (0x020EC, 0x020EF, 5, 0), (0x020F0, 0x020F0, 5, 1),
+    (0x02100, 0x02138, 1, 1), (0x02139, 0x0213A, 3, 0), (0x0213B, 0x0213B, 4, 0), (0x0213C, 0x0213C, 4, 1),
+    (0x0213D, 0x0214B, 3, 2), (0x0214C, 0x0214C, 4, 1), (0x0214D, 0x0214E, 5, 0), (0x0214F, 0x0214F, 5, 1),
+    (0x02150, 0x02152, 5, 2), (0x02153, 0x02182, 1, 1), (0x02183, 0x02183, 3, 0), (0x02184, 0x02184, 5, 0),
+    (0x02185, 0x02188, 5, 1), (0x02189, 0x02189, 5, 2), (0x0218A, 0x0218B, 8, 0), (0x02190, 0x021EA, 1, 1),
+    (0x021EB, 0x021F3, 3, 0), (0x021F4, 0x021FF, 3, 2), (0x02200, 0x022F1, 1, 1), (0x022F2, 0x022FF, 3, 2),
+    (0x02300, 0x02300, 1, 1), (0x02301, 0x02301, 3, 0), (0x02302, 0x0237A, 1, 1), (0x0237B, 0x0237B, 3, 0),
+    (0x0237C, 0x0237C, 3, 2), (0x0237D, 0x0239A, 3, 0), (0x0239B, 0x023CE, 3, 2), (0x023CF, 0x023D0, 4, 0),
+    (0x023D1, 0x023DB, 4, 1), (0x023DC, 0x023E7, 5, 0), (0x023E8, 0x023E8, 5, 2), (0x023E9, 0x023F3, 6, 0),
+    (0x023F4, 0x023FA, 7, 0), (0x023FB, 0x023FE, 9, 0), (0x023FF, 0x023FF, 10, 0), (0x02400, 0x02424, 1, 1),
+    (0x02425, 0x02426, 3, 0), (0x02427, 0x02429, 16, 0), (0x02440, 0x0244A, 1, 1), (0x02460, 0x024EA, 1, 1),
+    (0x024EB, 0x024FE, 3, 2), (0x024FF, 0x024FF, 4, 0), (0x02500, 0x02595, 1, 1), (0x02596, 0x0259F, 3, 2),
+    (0x025A0, 0x025EF, 1, 1), (0x025F0, 0x025F7, 3, 0), (0x025F8, 0x025FF, 3, 2), (0x02600, 0x02613, 1, 1),
+    (0x02614, 0x02615, 4, 0), (0x02616, 0x02617, 3, 2), (0x02618, 0x02618, 4, 1), (0x02619, 0x02619, 3, 0),
+    (0x0261A, 0x0266F, 1, 1), (0x02670, 0x02671, 3, 0), (0x02672, 0x0267D, 3, 2), (0x0267E, 0x0267F, 4, 1),
+    (0x02680, 0x02689, 3, 2), (0x0268A, 0x02691, 4, 0), (0x02692, 0x0269C, 4, 1), (0x0269D, 0x0269D, 5, 1),
+    (0x0269E, 0x0269F, 5, 2), (0x026A0, 0x026A1, 4, 0), (0x026A2, 0x026B1, 4, 1), (0x026B2, 0x026B2, 5, 0),
+    (0x026B3, 0x026BC, 5, 1), (0x026BD, 0x026BF, 5, 2), (0x026C0, 0x026C3, 5, 1), (0x026C4, 0x026CD, 5, 2),
+    (0x026CE, 0x026CE, 6, 0), (0x026CF, 0x026E1, 5, 2), (0x026E2, 0x026E2, 6, 0), (0x026E3, 0x026E3, 5, 2),
+    (0x026E4, 0x026E7, 6, 0), (0x026E8, 0x026FF, 5, 2), (0x02700, 0x02700, 7, 0), (0x02701, 0x02704, 1, 1),
+    (0x02705, 0x02705, 6, 0), (0x02706, 0x02709, 1, 1), (0x0270A, 0x0270B, 6, 0), (0x0270C, 0x02727, 1, 1),
+    (0x02728, 0x02728, 6, 0), (0x02729, 0x0274B, 1, 1), (0x0274C, 0x0274C, 6, 0), (0x0274D, 0x0274D, 1, 1),
+    (0x0274E, 0x0274E, 6, 0), (0x0274F, 0x02752, 1, 1), (0x02753, 0x02755, 6, 0), (0x02756, 0x02756, 1, 1),
+    (0x02757, 0x02757, 5, 2), (0x02758, 0x0275E, 1, 1), (0x0275F, 0x02760, 6, 0), (0x02761, 0x02767, 1, 1),
+    (0x02768, 0x02775, 3, 2), (0x02776, 0x02794, 1, 1), (0x02795, 0x02797, 6, 0), (0x02798, 0x027AF, 1, 1),
+    (0x027B0, 0x027B0, 6, 0), (0x027B1, 0x027BE, 1, 1), (0x027BF, 0x027BF, 6, 0), (0x027C0, 0x027C6, 4, 1),
+    (0x027C7, 0x027CA, 5, 0), (0x027CB, 0x027CB, 6, 1), (0x027CC, 0x027CC, 5, 1), (0x027CD, 0x027CD, 6, 1),
+    (0x027CE, 0x027CF, 6, 0), (0x027D0, 0x027EB, 3, 2), (0x027EC, 0x027EF, 5, 1), (0x027F0, 0x027FF, 3, 2),
+    (0x02800, 0x028FF, 3, 0), (0x02900, 0x02AFF, 3, 2), (0x02B00, 0x02B0D, 4, 0), (0x02B0E, 0x02B13, 4, 1),
+    (0x02B14, 0x02B1A, 5, 0), (0x02B1B, 0x02B1F, 5, 1), (0x02B20, 0x02B23, 5, 0), (0x02B24, 0x02B4C, 5, 1),
+    (0x02B4D, 0x02B4F, 7, 0), (0x02B50, 0x02B54, 5, 1), (0x02B55, 0x02B59, 5, 2), (0x02B5A, 0x02B73, 7, 0),
+    (0x02B76, 0x02B95, 7, 0), (0x02B96, 0x02B96, 16, 0), (0x02B97, 0x02B97, 13, 0), (0x02B98, 0x02BB9, 7, 0),
+    (0x02BBA, 0x02BBC, 11, 0), (0x02BBD, 0x02BC8, 7, 0), (0x02BC9, 0x02BC9, 12, 0), (0x02BCA, 0x02BD1, 7, 0),
+    (0x02BD2, 0x02BD2, 10, 0), (0x02BD3, 0x02BEB, 11, 0), (0x02BEC, 0x02BEF, 8, 0), (0x02BF0, 0x02BFE, 11, 0),
+    (0x02BFF, 0x02BFF, 12, 0), (0x02C00, 0x02C2E, 4, 1), (0x02C2F, 0x02C2F, 14, 0), (0x02C30, 0x02C5E, 4, 1),
+    (0x02C5F, 0x02C5F, 14, 0), (0x02C60, 0x02C6C, 5, 0), (0x02C6D, 0x02C6F, 5, 1), (0x02C70, 0x02C70, 5, 2),
+    (0x02C71, 0x02C73, 5, 1), (0x02C74, 0x02C77, 5, 0), (0x02C78, 0x02C7D, 5, 1), (0x02C7E, 0x02C7F, 5, 2),
+    (0x02C80, 0x02CEA, 4, 1), (0x02CEB, 0x02CF1, 5, 2), (0x02CF2, 0x02CF3, 6, 1), (0x02CF9, 0x02D25, 4, 1),
+    (0x02D27, 0x02D27, 6, 1), (0x02D2D, 0x02D2D, 6, 1), (0x02D30, 0x02D65, 4, 1), (0x02D66, 0x02D67, 6, 1),
+    (0x02D6F, 0x02D6F, 4, 1), (0x02D70, 0x02D70, 6, 0), (0x02D7F, 0x02D7F, 6, 0), (0x02D80, 0x02D96, 4, 1),
+    (0x02DA0, 0x02DA6, 4, 1), (0x02DA8, 0x02DAE, 4, 1), (0x02DB0, 0x02DB6, 4, 1), (0x02DB8, 0x02DBE, 4, 1),
+    (0x02DC0, 0x02DC6, 4, 1), (0x02DC8, 0x02DCE, 4, 1), (0x02DD0, 0x02DD6, 4, 1), (0x02DD8, 0x02DDE, 4, 1),
+    (0x02DE0, 0x02DFF, 5, 1), (0x02E00, 0x02E17, 4, 1), (0x02E18, 0x02E1B, 5, 1), (0x02E1C, 0x02E1D, 4, 1),
+    (0x02E1E, 0x02E30, 5, 1), (0x02E31, 0x02E31, 5, 2), (0x02E32, 0x02E3B, 6, 1), (0x02E3C, 0x02E42, 7, 0),
+    (0x02E43, 0x02E44, 9, 0), (0x02E45, 0x02E49, 10, 0), (0x02E4A, 0x02E4E, 11, 0), (0x02E4F, 0x02E4F, 12, 0),
+    (0x02E50, 0x02E52, 13, 0), (0x02E53, 0x02E5D, 14, 0), (0x02E80, 0x02E99, 3, 0), (0x02E9B, 0x02EF3, 3, 0),
+    (0x02F00, 0x02FD5, 3, 0), (0x02FF0, 0x02FFB, 3, 0), (0x02FFC, 0x02FFF, 15, 1), (0x03000, 0x03037, 1, 1),
+    (0x03038, 0x0303A, 3, 0), (0x0303B, 0x0303D, 3, 2), (0x0303E, 0x0303E, 3, 0), (0x0303F, 0x0303F, 1, 1),
+    (0x03041, 0x03094, 1, 1), (0x03095, 0x03096, 3, 2), (0x03099, 0x0309E, 1, 1), (0x0309F, 0x030A0, 3, 2),
+    (0x030A1, 0x030FE, 1, 1), (0x030FF, 0x030FF, 3, 2), (0x03105, 0x0312C, 1, 1), (0x0312D, 0x0312D, 5, 1),
+    (0x0312E, 0x0312E, 10, 0), (0x0312F, 0x0312F, 11, 0), (0x03131, 0x0318E, 1, 1), (0x03190, 0x0319F, 1, 1),
+    (0x031A0, 0x031B7, 3, 0), (0x031B8, 0x031BA, 6, 0), (0x031BB, 0x031BF, 13, 0), (0x031C0, 0x031CF, 4, 1),
+    (0x031D0, 0x031E3, 5, 1), (0x031EF, 0x031EF, 15, 1), (0x031F0, 0x031FF, 3, 2), (0x03200, 0x0321C, 1, 1),
+    (0x0321D, 0x0321E, 4, 0), (0x03220, 0x03243, 1, 1), (0x03244, 0x0324F, 5, 2), (0x03250, 0x03250, 4, 0),
+    (0x03251, 0x0325F, 3, 2), (0x03260, 0x0327B, 1, 1), (0x0327C, 0x0327D, 4, 0), (0x0327E, 0x0327E, 4, 1),
+    (0x0327F, 0x032B0, 1, 1), (0x032B1, 0x032BF, 3, 2), (0x032C0, 0x032CB, 1, 1), (0x032CC, 0x032CF, 4, 0),
+    (0x032D0, 0x032FE, 1, 1), (0x032FF, 0x032FF, 12, 1), (0x03300, 0x03376, 1, 1), (0x03377, 0x0337A, 4, 0),
+    (0x0337B, 0x033DD, 1, 1), (0x033DE, 0x033DF, 4, 0), (0x033E0, 0x033FE, 1, 1), (0x033FF, 0x033FF, 4, 0),
+    (0x03400, 0x04DB5, 3, 0), (0x04DB6, 0x04DBF, 13, 0), (0x04DC0, 0x04DFF, 4, 0), (0x04E00, 0x09FA5, 1, 1),
+    (0x09FA6, 0x09FBB, 4, 1), (0x09FBC, 0x09FC3, 5, 1), (0x09FC4, 0x09FCB, 5, 2), (0x09FCC, 0x09FCC, 6, 1),
+    (0x09FCD, 0x09FD5, 8, 0), (0x09FD6, 0x09FEA, 10, 0), (0x09FEB, 0x09FEF, 11, 0), (0x09FF0, 0x09FFC, 13, 0),
+    (0x09FFD, 0x09FFF, 14, 0), (0x0A000, 0x0A48C, 3, 0), (0x0A490, 0x0A4A1, 3, 0), (0x0A4A2, 0x0A4A3, 3, 2),
+    (0x0A4A4, 0x0A4B3, 3, 0), (0x0A4B4, 0x0A4B4, 3, 2), (0x0A4B5, 0x0A4C0, 3, 0), (0x0A4C1, 0x0A4C1, 3, 2),
+    (0x0A4C2, 0x0A4C4, 3, 0), (0x0A4C5, 0x0A4C5, 3, 2), (0x0A4C6, 0x0A4C6, 3, 0), (0x0A4D0, 0x0A4FF, 5, 2),
+    (0x0A500, 0x0A62B, 5, 1), (0x0A640, 0x0A65F, 5, 1), (0x0A660, 0x0A661, 6, 0), (0x0A662, 0x0A673, 5, 1),
+    (0x0A674, 0x0A67B, 6, 1), (0x0A67C, 0x0A697, 5, 1), (0x0A698, 0x0A69D, 7, 0), (0x0A69E, 0x0A69E, 8, 0),
+    (0x0A69F, 0x0A69F, 6, 1), (0x0A6A0, 0x0A6F7, 5, 2), (0x0A700, 0x0A716, 4, 1), (0x0A717, 0x0A71A, 5, 0),
+    (0x0A71B, 0x0A71F, 5, 1), (0x0A720, 0x0A721, 5, 0), (0x0A722, 0x0A78C, 5, 1), (0x0A78D, 0x0A78E, 6, 0),
+    (0x0A78F, 0x0A78F, 8, 0), (0x0A790, 0x0A791, 6, 0), (0x0A792, 0x0A793, 6, 1), (0x0A794, 0x0A79F, 7, 0),
+    (0x0A7A0, 0x0A7A9, 6, 0), (0x0A7AA, 0x0A7AA, 6, 1), (0x0A7AB, 0x0A7AD, 7, 0), (0x0A7AE, 0x0A7AE, 9, 0),
+    (0x0A7AF, 0x0A7AF, 11, 0), (0x0A7B0, 0x0A7B1, 7, 0), (0x0A7B2, 0x0A7B7, 8, 0), (0x0A7B8, 0x0A7B9, 11, 0),
+    (0x0A7BA, 0x0A7BF, 12, 0), (0x0A7C0, 0x0A7C1, 14, 0), (0x0A7C2, 0x0A7C6, 12, 0), (0x0A7C7, 0x0A7CA, 13, 0),
+    (0x0A7CB, 0x0A7CD, 16, 0), (0x0A7D0, 0x0A7D1, 14, 0), (0x0A7D3, 0x0A7D3, 14, 0), (0x0A7D5, 0x0A7D9, 14, 0),
+    (0x0A7DA, 0x0A7DC, 16, 0), (0x0A7F2, 0x0A7F4, 14, 0), (0x0A7F5, 0x0A7F6, 13, 0), (0x0A7F7, 0x0A7F7, 7, 0),
+    (0x0A7F8, 0x0A7F9, 6, 1), (0x0A7FA, 0x0A7FA, 6, 0), (0x0A7FB, 0x0A7FF, 5, 1), (0x0A800, 0x0A82B, 4, 1),
+    (0x0A82C, 0x0A82C, 13, 0), (0x0A830, 0x0A839, 5, 2), (0x0A840, 0x0A877, 5, 0), (0x0A880, 0x0A8C4, 5, 1),
+    (0x0A8C5, 0x0A8C5, 9, 0), (0x0A8CE, 0x0A8D9, 5, 1), (0x0A8E0, 0x0A8FB, 5, 2), (0x0A8FC, 0x0A8FD, 8, 0),
+    (0x0A8FE, 0x0A8FF, 11, 0), (0x0A900, 0x0A953, 5, 1), (0x0A95F, 0x0A95F, 5, 1), (0x0A960, 0x0A97C, 5, 2),
+    (0x0A980, 0x0A9CD, 5, 2), (0x0A9CF, 0x0A9D9, 5, 2), (0x0A9DE, 0x0A9DF, 5, 2), (0x0A9E0, 0x0A9FE, 7, 0),
+    (0x0AA00, 0x0AA36, 5, 1), (0x0AA40, 0x0AA4D, 5, 1), (0x0AA50, 0x0AA59, 5, 1), (0x0AA5C, 0x0AA5F, 5, 1),
+    (0x0AA60, 0x0AA7B, 5, 2), (0x0AA7C, 0x0AA7F, 7, 0), (0x0AA80, 0x0AAC2, 5, 2), (0x0AADB, 0x0AADF, 5, 2),
+    (0x0AAE0, 0x0AAF6, 6, 1), (0x0AB01, 0x0AB06, 6, 0), (0x0AB09, 0x0AB0E, 6, 0), (0x0AB11, 0x0AB16, 6, 0),
+    (0x0AB20, 0x0AB26, 6, 0), (0x0AB28, 0x0AB2E, 6, 0), (0x0AB30, 0x0AB5F, 7, 0), (0x0AB60, 0x0AB63, 8, 0),
+    (0x0AB64, 0x0AB65, 7, 0), (0x0AB66, 0x0AB67, 12, 0), (0x0AB68, 0x0AB6B, 13, 0), (0x0AB70, 0x0ABBF, 8, 0),
+    (0x0ABC0, 0x0ABED, 5, 2), (0x0ABF0, 0x0ABF9, 5, 2), (0x0AC00, 0x0D7A3, 2, 0), (0x0D7B0, 0x0D7C6, 5, 2),
+    (0x0D7CB, 0x0D7FB, 5, 2), (0x0D800, 0x0DFFF, 2, 0), (0x0E000, 0x0FA2D, 1, 1), (0x0FA2E, 0x0FA2F, 6, 1),
+    (0x0FA30, 0x0FA6A, 3, 2), (0x0FA6B, 0x0FA6D, 5, 2), (0x0FA70, 0x0FAD9, 4, 1), (0x0FB00, 0x0FB06, 1, 1),
+    (0x0FB13, 0x0FB17, 1, 1), (0x0FB1D, 0x0FB1D, 3, 0), (0x0FB1E, 0x0FB36, 1, 1), (0x0FB38, 0x0FB3C, 1, 1),
+    (0x0FB3E, 0x0FB3E, 1, 1), (0x0FB40, 0x0FB41, 1, 1), (0x0FB43, 0x0FB44, 1, 1), (0x0FB46, 0x0FBB1, 1, 1),
+    (0x0FBB2, 0x0FBC1, 6, 0), (0x0FBC2, 0x0FBC2, 14, 0), (0x0FBD3, 0x0FD3F, 1, 1), (0x0FD40, 0x0FD4F, 14, 0),
+    (0x0FD50, 0x0FD8F, 1, 1), (0x0FD92, 0x0FDC7, 1, 1), (0x0FDCF, 0x0FDCF, 14, 0), (0x0FDD0, 0x0FDEF, 3, 1),
+    (0x0FDF0, 0x0FDFB, 1, 1), (0x0FDFC, 0x0FDFC, 3, 2), (0x0FDFD, 0x0FDFD, 4, 0), (0x0FDFE, 0x0FDFF, 14, 0),
+    (0x0FE00, 0x0FE0F, 3, 2), (0x0FE10, 0x0FE19, 4, 1), (0x0FE20, 0x0FE23, 1, 1), (0x0FE24, 0x0FE26, 5, 1),
+    (0x0FE27, 0x0FE2D, 7, 0), (0x0FE2E, 0x0FE2F, 8, 0), (0x0FE30, 0x0FE44, 1, 1), (0x0FE45, 0x0FE46, 3, 2),
+    (0x0FE47, 0x0FE48, 4, 0), (0x0FE49, 0x0FE52, 1, 1), (0x0FE54, 0x0FE66, 1, 1), (0x0FE68, 0x0FE6B, 1, 1),
+    (0x0FE70, 0x0FE72, 1, 1), (0x0FE73, 0x0FE73, 3, 2), (0x0FE74, 0x0FE74, 1, 1), (0x0FE76, 0x0FEFC, 1, 1),
+    (0x0FEFF, 0x0FEFF, 1, 1), (0x0FF01, 0x0FF5E, 1, 1), (0x0FF5F, 0x0FF60, 3, 2), (0x0FF61, 0x0FFBE, 1, 1),
+    (0x0FFC2, 0x0FFC7, 1, 1), (0x0FFCA, 0x0FFCF, 1, 1), (0x0FFD2, 0x0FFD7, 1, 1), (0x0FFDA, 0x0FFDC, 1, 1),
+    (0x0FFE0, 0x0FFE6, 1, 1), (0x0FFE8, 0x0FFEE, 1, 1), (0x0FFF9, 0x0FFFB, 3, 0), (0x0FFFC, 0x0FFFC, 2, 1),
+    (0x0FFFD, 0x0FFFF, 1, 1), (0x10000, 0x1000B, 4, 0), (0x1000D, 0x10026, 4, 0), (0x10028, 0x1003A, 4, 0),
+    (0x1003C, 0x1003D, 4, 0), (0x1003F, 0x1004D, 4, 0), (0x10050, 0x1005D, 4, 0), (0x10080, 0x100FA, 4, 0),
+    (0x10100, 0x10102, 4, 0), (0x10107, 0x10133, 4, 0), (0x10137, 0x1013F, 4, 0), (0x10140, 0x1018A, 4, 1),
+    (0x1018B, 0x1018C, 7, 0), (0x1018D, 0x1018E, 9, 0), (0x10190, 0x1019B, 5, 1), (0x1019C, 0x1019C, 13, 0),
+    (0x101A0, 0x101A0, 7, 0), (0x101D0, 0x101FD, 5, 1), (0x10280, 0x1029C, 5, 1), (0x102A0, 0x102D0, 5, 1),
+    (0x102E0, 0x102FB, 7, 0), (0x10300, 0x1031E, 3, 1), (0x1031F, 0x1031F, 7, 0), (0x10320, 0x10323, 3, 1),
+    (0x1032D, 0x1032F, 10, 0), (0x10330, 0x1034A, 3, 1), (0x10350, 0x1037A, 7, 0), (0x10380, 0x1039D, 4, 0),
+    (0x1039F, 0x1039F, 4, 0), (0x103A0, 0x103C3, 4, 1), (0x103C8, 0x103D5, 4, 1), (0x10400, 0x10425, 3, 1),
+    (0x10426, 0x10427, 4, 0), (0x10428, 0x1044D, 3, 1), (0x1044E, 0x1049D, 4, 0), (0x104A0, 0x104A9, 4, 0),
+    (0x104B0, 0x104D3, 9, 0), (0x104D8, 0x104FB, 9, 0), (0x10500, 0x10527, 7, 0), (0x10530, 0x10563, 7, 0),
+    (0x1056F, 0x1056F, 7, 0), (0x10570, 0x1057A, 14, 0), (0x1057C, 0x1058A, 14, 0), (0x1058C, 0x10592, 14, 0),
+    (0x10594, 0x10595, 14, 0), (0x10597, 0x105A1, 14, 0), (0x105A3, 0x105B1, 14, 0), (0x105B3, 0x105B9, 14, 0),
+    (0x105BB, 0x105BC, 14, 0), (0x105C0, 0x105F3, 16, 0), (0x10600, 0x10736, 7, 0), (0x10740, 0x10755, 7, 0),
+    (0x10760, 0x10767, 7, 0), (0x10780, 0x10785, 14, 0), (0x10787, 0x107B0, 14, 0), (0x107B2, 0x107BA, 14, 0),
+    (0x10800, 0x10805, 4, 0), (0x10808, 0x10808, 4, 0), (0x1080A, 0x10835, 4, 0), (0x10837, 0x10838, 4, 0),
+    (0x1083C, 0x1083C, 4, 0), (0x1083F, 0x1083F, 4, 0), (0x10840, 0x10855, 5, 2), (0x10857, 0x1085F, 5, 2),
+    (0x10860, 0x1089E, 7, 0), (0x108A7, 0x108AF, 7, 0), (0x108E0, 0x108F2, 8, 0), (0x108F4, 0x108F5, 8, 0),
+    (0x108FB, 0x108FF, 8, 0), (0x10900, 0x10919, 5, 0), (0x1091A, 0x1091B, 5, 2), (0x1091F, 0x1091F, 5, 0),
+    (0x10920, 0x10939, 5, 1), (0x1093F, 0x1093F, 5, 1), (0x10980, 0x109B7, 6, 1), (0x109BC, 0x109BD, 8, 0),
+    (0x109BE, 0x109BF, 6, 1), (0x109C0, 0x109CF, 8, 0), (0x109D2, 0x109FF, 8, 0), (0x10A00, 0x10A03, 4, 1),
+    (0x10A05, 0x10A06, 4, 1), (0x10A0C, 0x10A13, 4, 1), (0x10A15, 0x10A17, 4, 1), (0x10A19, 0x10A33, 4, 1),
+    (0x10A34, 0x10A35, 11, 0), (0x10A38, 0x10A3A, 4, 1), (0x10A3F, 0x10A47, 4, 1), (0x10A48, 0x10A48, 11, 0),
+    (0x10A50, 0x10A58, 4, 1), (0x10A60, 0x10A7F, 5, 2), (0x10A80, 0x10A9F, 7, 0), (0x10AC0, 0x10AE6, 7, 0),
+    (0x10AEB, 0x10AF6, 7, 0), (0x10B00, 0x10B35, 5, 2), (0x10B39, 0x10B55, 5, 2), (0x10B58, 0x10B72, 5, 2),
+    (0x10B78, 0x10B7F, 5, 2), (0x10B80, 0x10B91, 7, 0), (0x10B99, 0x10B9C, 7, 0), (0x10BA9, 0x10BAF, 7, 0),
+    (0x10C00, 0x10C48, 5, 2), (0x10C80, 0x10CB2, 8, 0), (0x10CC0, 0x10CF2, 8, 0), (0x10CFA, 0x10CFF, 8, 0),
+    (0x10D00, 0x10D27, 11, 0), (0x10D30, 0x10D39, 11, 0), (0x10D40, 0x10D65, 16, 0), (0x10D69, 0x10D85, 16, 0),
+    (0x10D8E, 0x10D8F, 16, 0), (0x10E60, 0x10E7E, 5, 2), (0x10E80, 0x10EA9, 13, 0), (0x10EAB, 0x10EAD, 13, 0),
+    (0x10EB0, 0x10EB1, 13, 0), (0x10EC2, 0x10EC4, 16, 0), (0x10EFC, 0x10EFC, 16, 0), (0x10EFD, 0x10EFF, 15, 0),
+    (0x10F00, 0x10F27, 11, 0), (0x10F30, 0x10F59, 11, 0), (0x10F70, 0x10F89, 14, 0), (0x10FB0, 0x10FCB, 13, 0),
+    (0x10FE0, 0x10FF6, 12, 0), (0x11000, 0x1104D, 6, 0), (0x11052, 0x1106F, 6, 0), (0x11070, 0x11075, 14, 0),
+    (0x1107F, 0x1107F, 7, 0), (0x11080, 0x110C1, 5, 2), (0x110C2, 0x110C2, 14, 0), (0x110CD, 0x110CD, 11, 0),
+    (0x110D0, 0x110E8, 6, 1), (0x110F0, 0x110F9, 6, 1), (0x11100, 0x11134, 6, 1), (0x11136, 0x11143, 6, 1),
+    (0x11144, 0x11146, 11, 0), (0x11147, 0x11147, 13, 0), (0x11150, 0x11176, 7, 0), (0x11180, 0x111C8, 6, 1),
+    (0x111C9, 0x111CC, 8, 0), (0x111CD, 0x111CD, 7, 0), (0x111CE, 0x111CF, 13, 0), (0x111D0, 0x111D9, 6, 1),
+    (0x111DA, 0x111DA, 7, 0), (0x111DB, 0x111DF, 8, 0), (0x111E1, 0x111F4, 7, 0), (0x11200, 0x11211, 7, 0),
+    (0x11213, 0x1123D, 7, 0), (0x1123E, 0x1123E, 9, 0), (0x1123F, 0x11241, 15, 0), (0x11280, 0x11286, 8, 0),
+    (0x11288, 0x11288, 8, 0), (0x1128A, 0x1128D, 8, 0), (0x1128F, 0x1129D, 8, 0), (0x1129F, 0x112A9, 8, 0),
+    (0x112B0, 0x112EA, 7, 0), (0x112F0, 0x112F9, 7, 0), (0x11300, 0x11300, 8, 0), (0x11301, 0x11303, 7, 0),
+    (0x11305, 0x1130C, 7, 0), (0x1130F, 0x11310, 7, 0), (0x11313, 0x11328, 7, 0), (0x1132A, 0x11330, 7, 0),
+    (0x11332, 0x11333, 7, 0), (0x11335, 0x11339, 7, 0), (0x1133B, 0x1133B, 11, 0), (0x1133C, 0x11344, 7, 0),
+    (0x11347, 0x11348, 7, 0), (0x1134B, 0x1134D, 7, 0), (0x11350, 0x11350, 8, 0), (0x11357, 0x11357, 7, 0),
+    (0x1135D, 0x11363, 7, 0), (0x11366, 0x1136C, 7, 0), (0x11370, 0x11374, 7, 0), (0x11380, 0x11389, 16, 0),
+    (0x1138B, 0x1138B, 16, 0), (0x1138E, 0x1138E, 16, 0), (0x11390, 0x113B5, 16, 0), (0x113B7, 0x113C0, 16, 0),
+    (0x113C2, 0x113C2, 16, 0), (0x113C5, 0x113C5, 16, 0), (0x113C7, 0x113CA, 16, 0), (0x113CC, 0x113D5, 16, 0),
+    (0x113D7, 0x113D8, 16, 0), (0x113E1, 0x113E2, 16, 0), (0x11400, 0x11459, 9, 0), (0x1145A, 0x1145A, 13, 0),
+    (0x1145B, 0x1145B, 9, 0), (0x1145D, 0x1145D, 9, 0), (0x1145E, 0x1145E, 11, 0), (0x1145F, 0x1145F, 12, 0),
+    (0x11460, 0x11461, 13, 0), (0x11480, 0x114C7, 7, 0), (0x114D0, 0x114D9, 7, 0), (0x11580, 0x115B5, 7, 0),
+    (0x115B8, 0x115C9, 7, 0), (0x115CA, 0x115DD, 8, 0), (0x11600, 0x11644, 7, 0), (0x11650, 0x11659, 7, 0),
+    (0x11660, 0x1166C, 9, 0), (0x11680, 0x116B7, 6, 1), (0x116B8, 0x116B8, 12, 0), (0x116B9, 0x116B9, 14, 0),
+    (0x116C0, 0x116C9, 6, 1), (0x116D0, 0x116E3, 16, 0), (0x11700, 0x11719, 8, 0), (0x1171A, 0x1171A, 11, 0),
+    (0x1171D, 0x1172B, 8, 0), (0x11730, 0x1173F, 8, 0), (0x11740, 0x11746, 14, 0), (0x11800, 0x1183B, 11, 0),
+    (0x118A0, 0x118F2, 7, 0), (0x118FF, 0x118FF, 7, 0), (0x11900, 0x11906, 13, 0), (0x11909, 0x11909, 13, 0),
+    (0x1190C, 0x11913, 13, 0), (0x11915, 0x11916, 13, 0), (0x11918, 0x11935, 13, 0), (0x11937, 0x11938, 13, 0),
+    (0x1193B, 0x11946, 13, 0), (0x11950, 0x11959, 13, 0), (0x119A0, 0x119A7, 12, 0), (0x119AA, 0x119D7, 12, 0),
+    (0x119DA, 0x119E4, 12, 0), (0x11A00, 0x11A47, 10, 0), (0x11A50, 0x11A83, 10, 0), (0x11A84, 0x11A85, 12, 0),
+    (0x11A86, 0x11A9C, 10, 0), (0x11A9D, 0x11A9D, 11, 0), (0x11A9E, 0x11AA2, 10, 0), (0x11AB0, 0x11ABF, 14, 0),
+    (0x11AC0, 0x11AF8, 7, 0), (0x11B00, 0x11B09, 15, 0), (0x11BC0, 0x11BE1, 16, 0), (0x11BF0, 0x11BF9, 16, 0),
+    (0x11C00, 0x11C08, 9, 0), (0x11C0A, 0x11C36, 9, 0), (0x11C38, 0x11C45, 9, 0), (0x11C50, 0x11C6C, 9, 0),
+    (0x11C70, 0x11C8F, 9, 0), (0x11C92, 0x11CA7, 9, 0), (0x11CA9, 0x11CB6, 9, 0), (0x11D00, 0x11D06, 10, 0),
+    (0x11D08, 0x11D09, 10, 0), (0x11D0B, 0x11D36, 10, 0), (0x11D3A, 0x11D3A, 10, 0), (0x11D3C, 0x11D3D, 10, 0),
+    (0x11D3F, 0x11D47, 10, 0), (0x11D50, 0x11D59, 10, 0), (0x11D60, 0x11D65, 11, 0), (0x11D67, 0x11D68, 11, 0),
+    (0x11D6A, 0x11D8E, 11, 0), (0x11D90, 0x11D91, 11, 0), (0x11D93, 0x11D98, 11, 0), (0x11DA0, 0x11DA9, 11, 0),
+    (0x11EE0, 0x11EF8, 11, 0), (0x11F00, 0x11F10, 15, 0), (0x11F12, 0x11F3A, 15, 0), (0x11F3E, 0x11F59, 15, 0),
+    (0x11F5A, 0x11F5A, 16, 0), (0x11FB0, 0x11FB0, 13, 0), (0x11FC0, 0x11FF1, 12, 0), (0x11FFF, 0x11FFF, 12, 0),
+    (0x12000, 0x1236E, 5, 0), (0x1236F, 0x12398, 7, 0), (0x12399, 0x12399, 8, 0), (0x12400, 0x12462, 5, 0),
+    (0x12463, 0x1246E, 7, 0), (0x12470, 0x12473, 5, 0), (0x12474, 0x12474, 7, 0), (0x12480, 0x12543, 8, 0),
+    (0x12F90, 0x12FF2, 14, 0), (0x13000, 0x1342E, 5, 2), (0x1342F, 0x1342F, 15, 0), (0x13430, 0x13438, 12, 0),
+    (0x13439, 0x13455, 15, 0), (0x13460, 0x143FA, 16, 0), (0x14400, 0x14646, 8, 0), (0x16100, 0x16139, 16, 0),
+    (0x16800, 0x16A38, 6, 0), (0x16A40, 0x16A5E, 7, 0), (0x16A60, 0x16A69, 7, 0), (0x16A6E, 0x16A6F, 7, 0),
+    (0x16A70, 0x16ABE, 14, 0), (0x16AC0, 0x16AC9, 14, 0), (0x16AD0, 0x16AED, 7, 0), (0x16AF0, 0x16AF5, 7, 0),
+    (0x16B00, 0x16B45, 7, 0), (0x16B50, 0x16B59, 7, 0), (0x16B5B, 0x16B61, 7, 0), (0x16B63, 0x16B77, 7, 0),
+    (0x16B7D, 0x16B8F, 7, 0), (0x16D40, 0x16D79, 16, 0), (0x16E40, 0x16E9A, 11, 0), (0x16F00, 0x16F44, 6, 1),
+    (0x16F45, 0x16F4A, 12, 0), (0x16F4F, 0x16F4F, 12, 0), (0x16F50, 0x16F7E, 6, 1), (0x16F7F, 0x16F87, 12, 0),
+    (0x16F8F, 0x16F9F, 6, 1), (0x16FE0, 0x16FE0, 9, 0), (0x16FE1, 0x16FE1, 10, 0), (0x16FE2, 0x16FE3, 12, 0),
+    (0x16FE4, 0x16FE4, 13, 0), (0x16FF0, 0x16FF1, 13, 0), (0x17000, 0x187EC, 9, 0), (0x187ED, 0x187F1, 11, 0),
+    (0x187F2, 0x187F7, 12, 0), (0x18800, 0x18AF2, 9, 0), (0x18AF3, 0x18CD5, 13, 0), (0x18CFF, 0x18CFF, 16, 0),
+    (0x18D00, 0x18D08, 13, 0), (0x1AFF0, 0x1AFF3, 14, 0), (0x1AFF5, 0x1AFFB, 14, 0), (0x1AFFD, 0x1AFFE, 14, 0),
+    (0x1B000, 0x1B001, 6, 0), (0x1B002, 0x1B11E, 10, 0), (0x1B11F, 0x1B122, 14, 0), (0x1B132, 0x1B132, 15, 0),
+    (0x1B150, 0x1B152, 12, 0), (0x1B155, 0x1B155, 15, 0), (0x1B164, 0x1B167, 12, 0), (0x1B170, 0x1B2FB, 10, 0),
+    (0x1BC00, 0x1BC6A, 7, 0), (0x1BC70, 0x1BC7C, 7, 0), (0x1BC80, 0x1BC88, 7, 0), (0x1BC90, 0x1BC99, 7, 0),
+    (0x1BC9C, 0x1BCA3, 7, 0), (0x1CC00, 0x1CCF9, 16, 0), (0x1CD00, 0x1CEB3, 16, 0), (0x1CF00, 0x1CF2D, 14, 0),
+    (0x1CF30, 0x1CF46, 14, 0), (0x1CF50, 0x1CFC3, 14, 0), (0x1D000, 0x1D0F5, 3, 1), (0x1D100, 0x1D126, 3, 1),
+    (0x1D129, 0x1D129, 5, 1), (0x1D12A, 0x1D1DD, 3, 1), (0x1D1DE, 0x1D1E8, 8, 0), (0x1D1E9, 0x1D1EA, 14, 0),
+    (0x1D200, 0x1D245, 4, 1), (0x1D2C0, 0x1D2D3, 15, 0), (0x1D2E0, 0x1D2F3, 11, 0), (0x1D300, 0x1D356, 4, 0),
+    (0x1D360, 0x1D371, 5, 0), (0x1D372, 0x1D378, 11, 0), (0x1D400, 0x1D454, 3, 1), (0x1D456, 0x1D49C, 3, 1),
+    (0x1D49E, 0x1D49F, 3, 1), (0x1D4A2, 0x1D4A2, 3, 1), (0x1D4A5, 0x1D4A6, 3, 1), (0x1D4A9, 0x1D4AC, 3, 1),
+    (0x1D4AE, 0x1D4B9, 3, 1), (0x1D4BB, 0x1D4BB, 3, 1), (0x1D4BD, 0x1D4C0, 3, 1), (0x1D4C1, 0x1D4C1, 4, 0),
+    (0x1D4C2, 0x1D4C3, 3, 1), (0x1D4C5, 0x1D505, 3, 1), (0x1D507, 0x1D50A, 3, 1), (0x1D50D, 0x1D514, 3, 1),
+    (0x1D516, 0x1D51C, 3, 1), (0x1D51E, 0x1D539, 3, 1), (0x1D53B, 0x1D53E, 3, 1), (0x1D540, 0x1D544, 3, 1),
+    (0x1D546, 0x1D546, 3, 1), (0x1D54A, 0x1D550, 3, 1), (0x1D552, 0x1D6A3, 3, 1), (0x1D6A4, 0x1D6A5, 4, 1),
+    (0x1D6A8, 0x1D7C9, 3, 1), (0x1D7CA, 0x1D7CB, 5, 0), (0x1D7CE, 0x1D7FF, 3, 1), (0x1D800, 0x1DA8B, 8, 0),
+    (0x1DA9B, 0x1DA9F, 8, 0), (0x1DAA1, 0x1DAAF, 8, 0), (0x1DF00, 0x1DF1E, 14, 0), (0x1DF25, 0x1DF2A, 15, 0),
+    (0x1E000, 0x1E006, 9, 0), (0x1E008, 0x1E018, 9, 0), (0x1E01B, 0x1E021, 9, 0), (0x1E023, 0x1E024, 9, 0),
+    (0x1E026, 0x1E02A, 9, 0), (0x1E030, 0x1E06D, 15, 0), (0x1E08F, 0x1E08F, 15, 0), (0x1E100, 0x1E12C, 12, 0),
+    (0x1E130, 0x1E13D, 12, 0), (0x1E140, 0x1E149, 12, 0), (0x1E14E, 0x1E14F, 12, 0), (0x1E290, 0x1E2AE, 14, 0),
+    (0x1E2C0, 0x1E2F9, 12, 0), (0x1E2FF, 0x1E2FF, 12, 0), (0x1E4D0, 0x1E4F9, 15, 0), (0x1E5D0, 0x1E5FA, 16, 0),
+    (0x1E5FF, 0x1E5FF, 16, 0), (0x1E7E0, 0x1E7E6, 14, 0), (0x1E7E8, 0x1E7EB, 14, 0), (0x1E7ED, 0x1E7EE, 14, 0),
+    (0x1E7F0, 0x1E7FE, 14, 0), (0x1E800, 0x1E8C4, 7, 0), (0x1E8C7, 0x1E8D6, 7, 0), (0x1E900, 0x1E94A, 9, 0),
+    (0x1E94B, 0x1E94B, 12, 0), (0x1E950, 0x1E959, 9, 0), (0x1E95E, 0x1E95F, 9, 0), (0x1EC71, 0x1ECB4, 11, 0),
+    (0x1ED01, 0x1ED3D, 12, 0), (0x1EE00, 0x1EE03, 6, 1), (0x1EE05, 0x1EE1F, 6, 1), (0x1EE21, 0x1EE22, 6, 1),
+    (0x1EE24, 0x1EE24, 6, 1), (0x1EE27, 0x1EE27, 6, 1), (0x1EE29, 0x1EE32, 6, 1), (0x1EE34, 0x1EE37, 6, 1),
+    (0x1EE39, 0x1EE39, 6, 1), (0x1EE3B, 0x1EE3B, 6, 1), (0x1EE42, 0x1EE42, 6, 1), (0x1EE47, 0x1EE47, 6, 1),
+    (0x1EE49, 0x1EE49, 6, 1), (0x1EE4B, 0x1EE4B, 6, 1), (0x1EE4D, 0x1EE4F, 6, 1), (0x1EE51, 0x1EE52, 6, 1),
+    (0x1EE54, 0x1EE54, 6, 1), (0x1EE57, 0x1EE57, 6, 1), (0x1EE59, 0x1EE59, 6, 1), (0x1EE5B, 0x1EE5B, 6, 1),
+    (0x1EE5D, 0x1EE5D, 6, 1), (0x1EE5F, 0x1EE5F, 6, 1), (0x1EE61, 0x1EE62, 6, 1), (0x1EE64, 0x1EE64, 6, 1),
+    (0x1EE67, 0x1EE6A, 6, 1), (0x1EE6C, 0x1EE72, 6, 1), (0x1EE74, 0x1EE77, 6, 1), (0x1EE79, 0x1EE7C, 6, 1),
+    (0x1EE7E, 0x1EE7E, 6, 1), (0x1EE80, 0x1EE89, 6, 1), (0x1EE8B, 0x1EE9B, 6, 1), (0x1EEA1, 0x1EEA3, 6, 1),
+    (0x1EEA5, 0x1EEA9, 6, 1), (0x1EEAB, 0x1EEBB, 6, 1), (0x1EEF0, 0x1EEF1, 6, 1), (0x1F000, 0x1F02B, 5, 1),
+    (0x1F030, 0x1F093, 5, 1), (0x1F0A0, 0x1F0AE, 6, 0), (0x1F0B1, 0x1F0BE, 6, 0), (0x1F0BF, 0x1F0BF, 7, 0),
+    (0x1F0C1, 0x1F0CF, 6, 0), (0x1F0D1, 0x1F0DF, 6, 0), (0x1F0E0, 0x1F0F5, 7, 0), (0x1F100, 0x1F10A, 5, 2),
+    (0x1F10B, 0x1F10C, 7, 0), (0x1F10D, 0x1F10F, 13, 0), (0x1F110, 0x1F12E, 5, 2), (0x1F12F, 0x1F12F, 11, 0),
+    (0x1F130, 0x1F130, 6, 0), (0x1F131, 0x1F131, 5, 2), (0x1F132, 0x1F13C, 6, 0), (0x1F13D, 0x1F13D, 5, 2),
+    (0x1F13E, 0x1F13E, 6, 0), (0x1F13F, 0x1F13F, 5, 2), (0x1F140, 0x1F141, 6, 0), (0x1F142, 0x1F142, 5, 2),
+    (0x1F143, 0x1F145, 6, 0), (0x1F146, 0x1F146, 5, 2), (0x1F147, 0x1F149, 6, 0), (0x1F14A, 0x1F14E, 5, 2),
+    (0x1F14F, 0x1F156, 6, 0), (0x1F157, 0x1F157, 5, 2), (0x1F158, 0x1F15E, 6, 0), (0x1F15F, 0x1F15F, 5, 2),
+    (0x1F160, 0x1F169, 6, 0), (0x1F16A, 0x1F16B, 6, 1), (0x1F16C, 0x1F16C, 12, 0), (0x1F16D, 0x1F16F, 13, 0),
+    (0x1F170, 0x1F178, 6, 0), (0x1F179, 0x1F179, 5, 2), (0x1F17A, 0x1F17A, 6, 0), (0x1F17B, 0x1F17C, 5, 2),
+    (0x1F17D, 0x1F17E, 6, 0), (0x1F17F, 0x1F17F, 5, 2), (0x1F180, 0x1F189, 6, 0), (0x1F18A, 0x1F18D, 5, 2),
+    (0x1F18E, 0x1F18F, 6, 0), (0x1F190, 0x1F190, 5, 2), (0x1F191, 0x1F19A, 6, 0), (0x1F19B, 0x1F1AC, 9, 0),
+    (0x1F1AD, 0x1F1AD, 13, 0), (0x1F1E6, 0x1F1FF, 6, 0), (0x1F200, 0x1F200, 5, 2), (0x1F201, 0x1F202, 6, 0),
+    (0x1F210, 0x1F231, 5, 2), (0x1F232, 0x1F23A, 6, 0), (0x1F23B, 0x1F23B, 9, 0), (0x1F240, 0x1F248, 5, 2),
+    (0x1F250, 0x1F251, 6, 0), (0x1F260, 0x1F265, 10, 0), (0x1F300, 0x1F320, 6, 0), (0x1F321, 0x1F32C, 7, 0),
+    (0x1F32D, 0x1F32F, 8, 0), (0x1F330, 0x1F335, 6, 0), (0x1F336, 0x1F336, 7, 0), (0x1F337, 0x1F37C, 6, 0),
+    (0x1F37D, 0x1F37D, 7, 0), (0x1F37E, 0x1F37F, 8, 0), (0x1F380, 0x1F393, 6, 0), (0x1F394, 0x1F39F, 7, 0),
+    (0x1F3A0, 0x1F3C4, 6, 0), (0x1F3C5, 0x1F3C5, 7, 0), (0x1F3C6, 0x1F3CA, 6, 0), (0x1F3CB, 0x1F3CE, 7, 0),
+    (0x1F3CF, 0x1F3D3, 8, 0), (0x1F3D4, 0x1F3DF, 7, 0), (0x1F3E0, 0x1F3F0, 6, 0), (0x1F3F1, 0x1F3F7, 7, 0),
+    (0x1F3F8, 0x1F3FF, 8, 0), (0x1F400, 0x1F43E, 6, 0), (0x1F43F, 0x1F43F, 7, 0), (0x1F440, 0x1F440, 6, 0),
+    (0x1F441, 0x1F441, 7, 0), (0x1F442, 0x1F4F7, 6, 0), (0x1F4F8, 0x1F4F8, 7, 0), (0x1F4F9, 0x1F4FC, 6, 0),
+    (0x1F4FD, 0x1F4FE, 7, 0), (0x1F4FF, 0x1F4FF, 8, 0), (0x1F500, 0x1F53D, 6, 0), (0x1F53E, 0x1F53F, 7, 0),
+    (0x1F540, 0x1F543, 6, 1), (0x1F544, 0x1F54A, 7, 0), (0x1F54B, 0x1F54F, 8, 0), (0x1F550, 0x1F567, 6, 0),
+    (0x1F568, 0x1F579, 7, 0), (0x1F57A, 0x1F57A, 9, 0), (0x1F57B, 0x1F5A3, 7, 0), (0x1F5A4, 0x1F5A4, 9, 0),
+    (0x1F5A5, 0x1F5FA, 7, 0), (0x1F5FB, 0x1F5FF, 6, 0), (0x1F600, 0x1F600, 6, 1), (0x1F601, 0x1F610, 6, 0),
+    (0x1F611, 0x1F611, 6, 1), (0x1F612, 0x1F614, 6, 0), (0x1F615, 0x1F615, 6, 1), (0x1F616, 0x1F616, 6, 0),
+    (0x1F617, 0x1F617, 6, 1), (0x1F618, 0x1F618, 6, 0), (0x1F619, 0x1F619, 6, 1), (0x1F61A, 0x1F61A, 6, 0),
+    (0x1F61B, 0x1F61B, 6, 1), (0x1F61C, 0x1F61E, 6, 0), (0x1F61F, 0x1F61F, 6, 1), (0x1F620, 0x1F625, 6, 0),
+    (0x1F626, 0x1F627, 6, 1), (0x1F628, 0x1F62B, 6, 0), (0x1F62C, 0x1F62C, 6, 1), (0x1F62D, 0x1F62D, 6, 0),
+    (0x1F62E, 0x1F62F, 6, 1), (0x1F630, 0x1F633, 6, 0), (0x1F634, 0x1F634, 6, 1), (0x1F635, 0x1F640, 6, 0),
+    (0x1F641, 0x1F642, 7, 0), (0x1F643, 0x1F644, 8, 0), (0x1F645, 0x1F64F, 6, 0), (0x1F650, 0x1F67F, 7, 0),
+    (0x1F680, 0x1F6C5, 6, 0), (0x1F6C6, 0x1F6CF, 7, 0), (0x1F6D0, 0x1F6D0, 8, 0), (0x1F6D1, 0x1F6D2, 9, 0),
+    (0x1F6D3, 0x1F6D4, 10, 0), (0x1F6D5, 0x1F6D5, 12, 0), (0x1F6D6, 0x1F6D7, 13, 0), (0x1F6DC, 0x1F6DC, 15, 0),
+    (0x1F6DD, 0x1F6DF, 14, 0), (0x1F6E0, 0x1F6EC, 7, 0), (0x1F6F0, 0x1F6F3, 7, 0), (0x1F6F4, 0x1F6F6, 9, 0),
+    (0x1F6F7, 0x1F6F8, 10, 0), (0x1F6F9, 0x1F6F9, 11, 0), (0x1F6FA, 0x1F6FA, 12, 0), (0x1F6FB, 0x1F6FC, 13, 0),
+    (0x1F700, 0x1F773, 6, 0), (0x1F774, 0x1F776, 15, 0), (0x1F77B, 0x1F77F, 15, 0), (0x1F780, 0x1F7D4, 7, 0),
+    (0x1F7D5, 0x1F7D8, 11, 0), (0x1F7D9, 0x1F7D9, 15, 0), (0x1F7E0, 0x1F7EB, 12, 0), (0x1F7F0, 0x1F7F0, 14, 0),
+    (0x1F800, 0x1F80B, 7, 0), (0x1F810, 0x1F847, 7, 0), (0x1F850, 0x1F859, 7, 0), (0x1F860, 0x1F887, 7, 0),
+    (0x1F890, 0x1F8AD, 7, 0), (0x1F8B0, 0x1F8B1, 13, 0), (0x1F8B2, 0x1F8BB, 16, 0), (0x1F8C0, 0x1F8C1, 16, 0),
+    (0x1F900, 0x1F90B, 10, 0), (0x1F90C, 0x1F90C, 13, 0), (0x1F90D, 0x1F90F, 12, 0), (0x1F910, 0x1F918, 8, 0),
+    (0x1F919, 0x1F91E, 9, 0), (0x1F91F, 0x1F91F, 10, 0), (0x1F920, 0x1F927, 9, 0), (0x1F928, 0x1F92F, 10, 0),
+    (0x1F930, 0x1F930, 9, 0), (0x1F931, 0x1F932, 10, 0), (0x1F933, 0x1F93E, 9, 0), (0x1F93F, 0x1F93F, 12, 0),
+    (0x1F940, 0x1F94B, 9, 0), (0x1F94C, 0x1F94C, 10, 0), (0x1F94D, 0x1F94F, 11, 0), (0x1F950, 0x1F95E, 9, 0),
+    (0x1F95F, 0x1F96B, 10, 0), (0x1F96C, 0x1F970, 11, 0), (0x1F971, 0x1F971, 12, 0), (0x1F972, 0x1F972, 13, 0),
+    (0x1F973, 0x1F976, 11, 0), (0x1F977, 0x1F978, 13, 0), (0x1F979, 0x1F979, 14, 0), (0x1F97A, 0x1F97A, 11, 0),
+    (0x1F97B, 0x1F97B, 12, 0), (0x1F97C, 0x1F97F, 11, 0), (0x1F980, 0x1F984, 8, 0), (0x1F985, 0x1F991, 9, 0),
+    (0x1F992, 0x1F997, 10, 0), (0x1F998, 0x1F9A2, 11, 0), (0x1F9A3, 0x1F9A4, 13, 0), (0x1F9A5, 0x1F9AA, 12, 0),
+    (0x1F9AB, 0x1F9AD, 13, 0), (0x1F9AE, 0x1F9AF, 12, 0), (0x1F9B0, 0x1F9B9, 11, 0), (0x1F9BA, 0x1F9BF, 12, 0),
+    (0x1F9C0, 0x1F9C0, 8, 0), (0x1F9C1, 0x1F9C2, 11, 0), (0x1F9C3, 0x1F9CA, 12, 0), (0x1F9CB, 0x1F9CB, 13, 0),
+    (0x1F9CC, 0x1F9CC, 14, 0), (0x1F9CD, 0x1F9CF, 12, 0), (0x1F9D0, 0x1F9E6, 10, 0), (0x1F9E7, 0x1F9FF, 11, 0),
+    (0x1FA00, 0x1FA53, 12, 0), (0x1FA60, 0x1FA6D, 11, 0), (0x1FA70, 0x1FA73, 12, 0), (0x1FA74, 0x1FA74, 13, 0),
+    (0x1FA75, 0x1FA77, 15, 0), (0x1FA78, 0x1FA7A, 12, 0), (0x1FA7B, 0x1FA7C, 14, 0), (0x1FA80, 0x1FA82, 12, 0),
+    (0x1FA83, 0x1FA86, 13, 0), (0x1FA87, 0x1FA88, 15, 0), (0x1FA89, 0x1FA89, 16, 0), (0x1FA8F, 0x1FA8F, 16, 0),
+    (0x1FA90, 0x1FA95, 12, 0), (0x1FA96, 0x1FAA8, 13, 0), (0x1FAA9, 0x1FAAC, 14, 0), (0x1FAAD, 0x1FAAF, 15, 0),
+    (0x1FAB0, 0x1FAB6, 13, 0), (0x1FAB7, 0x1FABA, 14, 0), (0x1FABB, 0x1FABD, 15, 0), (0x1FABE, 0x1FABE, 16, 0),
+    (0x1FABF, 0x1FABF, 15, 0), (0x1FAC0, 0x1FAC2, 13, 0), (0x1FAC3, 0x1FAC5, 14, 0), (0x1FAC6, 0x1FAC6, 16, 0),
+    (0x1FACE, 0x1FACF, 15, 0), (0x1FAD0, 0x1FAD6, 13, 0), (0x1FAD7, 0x1FAD9, 14, 0), (0x1FADA, 0x1FADB, 15, 0),
+    (0x1FADC, 0x1FADC, 16, 0), (0x1FADF, 0x1FADF, 16, 0), (0x1FAE0, 0x1FAE7, 14, 0), (0x1FAE8, 0x1FAE8, 15, 0),
+    (0x1FAE9, 0x1FAE9, 16, 0), (0x1FAF0, 0x1FAF6, 14, 0), (0x1FAF7, 0x1FAF8, 15, 0), (0x1FB00, 0x1FB92, 13, 0),
+    (0x1FB94, 0x1FBCA, 13, 0), (0x1FBCB, 0x1FBEF, 16, 0), (0x1FBF0, 0x1FBF9, 13, 0), (0x1FBFA, 0x1FBFA, 16, 0),
+    (0x1FFFE, 0x1FFFF, 2, 0), (0x20000, 0x2A6D6, 3, 1), (0x2A6D7, 0x2A6DD, 13, 0), (0x2A6DE, 0x2A6DF, 14, 0),
+    (0x2A700, 0x2B734, 5, 2), (0x2B735, 0x2B738, 14, 0), (0x2B739, 0x2B739, 15, 0), (0x2B740, 0x2B81D, 6, 0),
+    (0x2B820, 0x2CEA1, 8, 0), (0x2CEB0, 0x2EBE0, 10, 0), (0x2EBF0, 0x2EE5D, 15, 1), (0x2F800, 0x2FA1D, 3, 1),
+    (0x2FFFE, 0x2FFFF, 2, 0), (0x30000, 0x3134A, 13, 0), (0x31350, 0x323AF, 15, 0), (0x3FFFE, 0x3FFFF, 2, 0),
+    (0x4FFFE, 0x4FFFF, 2, 0), (0x5FFFE, 0x5FFFF, 2, 0), (0x6FFFE, 0x6FFFF, 2, 0), (0x7FFFE, 0x7FFFF, 2, 0),
+    (0x8FFFE, 0x8FFFF, 2, 0), (0x9FFFE, 0x9FFFF, 2, 0), (0xAFFFE, 0xAFFFF, 2, 0), (0xBFFFE, 0xBFFFF, 2, 0),
+    (0xCFFFE, 0xCFFFF, 2, 0), (0xDFFFE, 0xDFFFF, 2, 0), (0xE0001, 0xE0001, 3, 1), (0xE0020, 0xE007F, 3, 1),
+    (0xE0100, 0xE01EF, 4, 0), (0xEFFFE, 0x10FFFF, 2, 0),
+];
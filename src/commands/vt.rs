@@ -0,0 +1,599 @@
+//! In-memory virtual terminal for deterministic output testing.
+//!
+//! Implements the common subset of VT100/xterm behavior used by TUI programs:
+//! cursor movement, erase, insert/delete, scrolling, SGR attributes and
+//! autowrap. Cell widths come from `runefix-core`, so wide and zero-width
+//! clusters occupy the same cells they would under the chosen `LayoutPolicy`.
+
+use crate::commands::policy::LayoutPolicy;
+use crate::commands::tabs::TabStops;
+use serde_json::{Value, json};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Foreground or background color set via SGR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    // 256-color palette index (0–15 are the classic ANSI colors)
+    Indexed(u8),
+
+    // 24-bit true color
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    fn to_json(self) -> Value {
+        match self {
+            Color::Indexed(i) => json!(i),
+            Color::Rgb(r, g, b) => json!(format!("#{r:02x}{g:02x}{b:02x}")),
+        }
+    }
+}
+
+/// Rendition attributes attached to each cell.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Attrs {
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub blink: bool,
+    pub reverse: bool,
+    pub strike: bool,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+impl Attrs {
+    /// Returns only the non-default attributes as a JSON object.
+    fn to_json(&self) -> Value {
+        let mut map = serde_json::Map::new();
+        for (key, on) in [
+            ("bold", self.bold),
+            ("dim", self.dim),
+            ("italic", self.italic),
+            ("underline", self.underline),
+            ("blink", self.blink),
+            ("reverse", self.reverse),
+            ("strike", self.strike),
+        ] {
+            if on {
+                map.insert(key.into(), json!(true));
+            }
+        }
+        if let Some(fg) = self.fg {
+            map.insert("fg".into(), fg.to_json());
+        }
+        if let Some(bg) = self.bg {
+            map.insert("bg".into(), bg.to_json());
+        }
+        Value::Object(map)
+    }
+}
+
+/// A single screen cell.
+///
+/// Wide clusters store their text in the first cell; the following cells
+/// are continuations with empty text and `width == 0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    pub text: String,
+    pub width: usize,
+    pub attrs: Attrs,
+}
+
+impl Cell {
+    /// Blank cell carrying only the background of `attrs` (xterm BCE behavior).
+    fn blank(attrs: &Attrs) -> Self {
+        Cell {
+            text: " ".to_string(),
+            width: 1,
+            attrs: Attrs {
+                bg: attrs.bg,
+                ..Attrs::default()
+            },
+        }
+    }
+
+    fn is_continuation(&self) -> bool {
+        self.width == 0
+    }
+}
+
+/// Virtual terminal screen.
+pub struct Screen<'p> {
+    cols: usize,
+    rows: usize,
+    grid: Vec<Vec<Cell>>,
+    row: usize,
+    col: usize,
+    attrs: Attrs,
+    wrap_pending: bool,
+    saved: (usize, usize, Attrs),
+    responses: String,
+    tabs: TabStops,
+    policy: &'p LayoutPolicy,
+}
+
+impl<'p> Screen<'p> {
+    /// Create an empty `cols` × `rows` screen (both must be at least 1).
+    pub fn new(cols: usize, rows: usize, tabs: TabStops, policy: &'p LayoutPolicy) -> Self {
+        let blank = Cell::blank(&Attrs::default());
+        Screen {
+            cols,
+            rows,
+            grid: vec![vec![blank; cols]; rows],
+            row: 0,
+            col: 0,
+            attrs: Attrs::default(),
+            wrap_pending: false,
+            saved: (0, 0, Attrs::default()),
+            responses: String::new(),
+            tabs,
+            policy,
+        }
+    }
+
+    /// Drain replies the terminal would send back (e.g. cursor position reports).
+    pub fn take_responses(&mut self) -> String {
+        std::mem::take(&mut self.responses)
+    }
+
+    /// Visible text of each row, with trailing blanks trimmed.
+    pub fn lines(&self) -> Vec<String> {
+        self.grid
+            .iter()
+            .map(|row| {
+                let line: String = row.iter().map(|c| c.text.as_str()).collect();
+                line.trim_end_matches(' ').to_string()
+            })
+            .collect()
+    }
+
+    /// Full screen state (size, cursor, lines and per-cell attributes) as JSON.
+    pub fn to_json(&self) -> Value {
+        let cells: Vec<Value> = self
+            .grid
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|c| {
+                        json!({
+                            "text": c.text,
+                            "width": c.width,
+                            "attrs": c.attrs.to_json(),
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        json!({
+            "cols": self.cols,
+            "rows": self.rows,
+            "cursor": { "row": self.row, "col": self.col },
+            "lines": self.lines(),
+            "cells": cells,
+        })
+    }
+
+    /// Feed terminal output (text and escape sequences) into the screen.
+    pub fn feed(&mut self, input: &str) {
+        let mut rest = input;
+
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('\u{1B}') {
+                rest = self.escape(after);
+                continue;
+            }
+
+            // Plain text up to the next escape
+            let end = rest.find('\u{1B}').unwrap_or(rest.len());
+            for g in rest[..end].graphemes(true) {
+                match g {
+                    "\r\n" => {
+                        self.carriage_return();
+                        self.newline();
+                    }
+                    "\r" => self.carriage_return(),
+                    // Output is assumed to be post-`onlcr`: LF also returns the carriage
+                    "\n" | "\u{0B}" | "\u{0C}" => self.newline(),
+                    "\u{8}" => {
+                        self.wrap_pending = false;
+                        self.col = self.col.saturating_sub(1);
+                    }
+                    "\t" => {
+                        self.wrap_pending = false;
+                        self.col = self.tabs.next_stop(self.col).min(self.cols - 1);
+                    }
+                    g if g.chars().all(char::is_control) => {}
+                    g => self.put(g),
+                }
+            }
+            rest = &rest[end..];
+        }
+    }
+
+    /// Handle an escape sequence (input starts right after `ESC`); returns the remainder.
+    fn escape<'a>(&mut self, after: &'a str) -> &'a str {
+        let mut chars = after.chars();
+        let Some(kind) = chars.next() else {
+            return "";
+        };
+
+        match kind {
+            // CSI: parameters, intermediates, then a final byte in 0x40–0x7E
+            '[' => {
+                let body = chars.as_str();
+                let end = body
+                    .find(|c: char| ('\u{40}'..='\u{7E}').contains(&c))
+                    .unwrap_or(body.len());
+                match body[end..].chars().next() {
+                    Some(action) => {
+                        self.csi(&body[..end], action);
+                        &body[end + action.len_utf8()..]
+                    }
+                    None => "",
+                }
+            }
+            // OSC / DCS / APC / PM: skip until BEL or ST
+            ']' | 'P' | '_' | '^' => {
+                let body = chars.as_str();
+                match body.find(['\u{07}', '\u{1B}']) {
+                    Some(i) if body[i..].starts_with("\u{1B}\\") => &body[i + 2..],
+                    Some(i) => &body[i + 1..],
+                    None => "",
+                }
+            }
+            // Charset designation takes one more byte
+            '(' | ')' | '*' | '+' => {
+                chars.next();
+                chars.as_str()
+            }
+            '7' => {
+                self.saved = (self.row, self.col, self.attrs.clone());
+                chars.as_str()
+            }
+            '8' => {
+                self.restore_cursor();
+                chars.as_str()
+            }
+            'D' => {
+                self.index();
+                chars.as_str()
+            }
+            'E' => {
+                self.newline();
+                chars.as_str()
+            }
+            'M' => {
+                self.reverse_index();
+                chars.as_str()
+            }
+            'c' => {
+                *self = Screen::new(self.cols, self.rows, self.tabs.clone(), self.policy);
+                chars.as_str()
+            }
+            _ => chars.as_str(),
+        }
+    }
+
+    /// Apply a CSI sequence.
+    fn csi(&mut self, params: &str, action: char) {
+        // Private sequences (`?25h`, `>c`, ...) don't affect the grid
+        if params.starts_with(['?', '>', '<', '=']) {
+            return;
+        }
+
+        let args: Vec<usize> = params
+            .split([';', ':'])
+            .map(|p| p.parse().unwrap_or(0))
+            .collect();
+        let arg = |i: usize| args.get(i).copied().unwrap_or(0);
+        let n = arg(0).max(1);
+
+        // SGR and status reports keep a pending autowrap; everything else cancels it
+        if !matches!(action, 'm' | 'n') {
+            self.wrap_pending = false;
+        }
+        match action {
+            'A' => self.row = self.row.saturating_sub(n),
+            'B' | 'e' => self.row = self.row.saturating_add(n).min(self.rows - 1),
+            'C' | 'a' => self.col = self.col.saturating_add(n).min(self.cols - 1),
+            'D' => self.col = self.col.saturating_sub(n),
+            'E' => {
+                self.row = self.row.saturating_add(n).min(self.rows - 1);
+                self.col = 0;
+            }
+            'F' => {
+                self.row = self.row.saturating_sub(n);
+                self.col = 0;
+            }
+            'G' | '`' => self.col = (n - 1).min(self.cols - 1),
+            'd' => self.row = (n - 1).min(self.rows - 1),
+            'H' | 'f' => {
+                self.row = (arg(0).max(1) - 1).min(self.rows - 1);
+                self.col = (arg(1).max(1) - 1).min(self.cols - 1);
+            }
+            'J' => match arg(0) {
+                0 => {
+                    self.erase_line(self.row, self.col, self.cols);
+                    for r in self.row + 1..self.rows {
+                        self.erase_line(r, 0, self.cols);
+                    }
+                }
+                1 => {
+                    for r in 0..self.row {
+                        self.erase_line(r, 0, self.cols);
+                    }
+                    self.erase_line(self.row, 0, self.col + 1);
+                }
+                _ => {
+                    for r in 0..self.rows {
+                        self.erase_line(r, 0, self.cols);
+                    }
+                }
+            },
+            'K' => match arg(0) {
+                0 => self.erase_line(self.row, self.col, self.cols),
+                1 => self.erase_line(self.row, 0, self.col + 1),
+                _ => self.erase_line(self.row, 0, self.cols),
+            },
+            'X' => self.erase_line(self.row, self.col, self.col.saturating_add(n)),
+            '@' => {
+                self.split_wide(self.row, self.col);
+                let blank = Cell::blank(&self.attrs);
+                let line = &mut self.grid[self.row];
+                for _ in 0..n.min(self.cols - self.col) {
+                    line.insert(self.col, blank.clone());
+                }
+                line.truncate(self.cols);
+                self.fix_edge(self.row);
+            }
+            'P' => {
+                self.split_wide(self.row, self.col);
+                let count = n.min(self.cols - self.col);
+                self.split_wide(self.row, self.col + count);
+                let blank = Cell::blank(&self.attrs);
+                let line = &mut self.grid[self.row];
+                line.drain(self.col..self.col + count);
+                line.resize(self.cols, blank);
+            }
+            'L' => {
+                for _ in 0..n.min(self.rows - self.row) {
+                    self.grid.pop();
+                    self.grid
+                        .insert(self.row, vec![Cell::blank(&self.attrs); self.cols]);
+                }
+            }
+            'M' => {
+                for _ in 0..n.min(self.rows - self.row) {
+                    self.grid.remove(self.row);
+                    self.grid.push(vec![Cell::blank(&self.attrs); self.cols]);
+                }
+            }
+            'S' => self.scroll_up(n),
+            'T' => self.scroll_down(n),
+            'm' => self.sgr(&args),
+            's' => self.saved = (self.row, self.col, self.attrs.clone()),
+            'u' => self.restore_cursor(),
+            // Device status report: 5 = status, 6 = cursor position (1-based)
+            'n' => match arg(0) {
+                5 => self.responses.push_str("\u{1B}[0n"),
+                6 => {
+                    let report = format!("\u{1B}[{};{}R", self.row + 1, self.col + 1);
+                    self.responses.push_str(&report);
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Apply SGR (Select Graphic Rendition) parameters.
+    fn sgr(&mut self, args: &[usize]) {
+        let mut i = 0;
+        while i < args.len() {
+            let a = &mut self.attrs;
+            match args[i] {
+                0 => *a = Attrs::default(),
+                1 => a.bold = true,
+                2 => a.dim = true,
+                3 => a.italic = true,
+                4 => a.underline = true,
+                5 | 6 => a.blink = true,
+                7 => a.reverse = true,
+                9 => a.strike = true,
+                22 => {
+                    a.bold = false;
+                    a.dim = false;
+                }
+                23 => a.italic = false,
+                24 => a.underline = false,
+                25 => a.blink = false,
+                27 => a.reverse = false,
+                29 => a.strike = false,
+                c @ 30..=37 => a.fg = Some(Color::Indexed((c - 30) as u8)),
+                39 => a.fg = None,
+                c @ 40..=47 => a.bg = Some(Color::Indexed((c - 40) as u8)),
+                49 => a.bg = None,
+                c @ 90..=97 => a.fg = Some(Color::Indexed((c - 90 + 8) as u8)),
+                c @ 100..=107 => a.bg = Some(Color::Indexed((c - 100 + 8) as u8)),
+                c @ (38 | 48) => {
+                    // Extended colors: `38;5;N` or `38;2;R;G;B`
+                    let color = match args.get(i + 1) {
+                        Some(5) => {
+                            let idx = args.get(i + 2).copied().unwrap_or(0);
+                            i += 2;
+                            Some(Color::Indexed(idx as u8))
+                        }
+                        Some(2) => {
+                            let ch = |k: usize| args.get(i + k).copied().unwrap_or(0) as u8;
+                            let rgb = Color::Rgb(ch(2), ch(3), ch(4));
+                            i += 4;
+                            Some(rgb)
+                        }
+                        _ => None,
+                    };
+                    if c == 38 {
+                        a.fg = color;
+                    } else {
+                        a.bg = color;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+
+    /// Write a printable grapheme at the cursor, wrapping as needed.
+    fn put(&mut self, g: &str) {
+        let w = self.policy.display_width(g).min(self.cols);
+
+        // Zero-width clusters attach to the previously written cell
+        if w == 0 {
+            let mut c = if self.wrap_pending {
+                self.col
+            } else {
+                self.col.saturating_sub(1)
+            };
+            while c > 0 && self.grid[self.row][c].is_continuation() {
+                c -= 1;
+            }
+            self.grid[self.row][c].text.push_str(g);
+            return;
+        }
+
+        if self.wrap_pending {
+            self.carriage_return();
+            self.index();
+        }
+
+        // A wide cluster that does not fit wraps early, leaving the last cell blank
+        if self.col + w > self.cols {
+            self.erase_line(self.row, self.col, self.cols);
+            self.carriage_return();
+            self.index();
+        }
+
+        self.split_wide(self.row, self.col);
+        self.split_wide(self.row, self.col + w);
+
+        let line = &mut self.grid[self.row];
+        line[self.col] = Cell {
+            text: g.to_string(),
+            width: w,
+            attrs: self.attrs.clone(),
+        };
+        for cell in &mut line[self.col + 1..self.col + w] {
+            *cell = Cell {
+                text: String::new(),
+                width: 0,
+                attrs: self.attrs.clone(),
+            };
+        }
+
+        self.col += w;
+        if self.col >= self.cols {
+            self.col = self.cols - 1;
+            self.wrap_pending = true;
+        }
+    }
+
+    /// If `col` falls inside a wide cluster, blank the whole cluster.
+    fn split_wide(&mut self, row: usize, col: usize) {
+        if col == 0 || col >= self.cols || !self.grid[row][col].is_continuation() {
+            return;
+        }
+
+        let mut head = col;
+        while head > 0 && self.grid[row][head].is_continuation() {
+            head -= 1;
+        }
+        let blank = Cell::blank(&self.attrs);
+        let line = &mut self.grid[row];
+        line[head] = blank.clone();
+        let mut c = head + 1;
+        while c < self.cols && line[c].is_continuation() {
+            line[c] = blank.clone();
+            c += 1;
+        }
+    }
+
+    /// Blank a wide cluster cut off at the right edge after an insert.
+    fn fix_edge(&mut self, row: usize) {
+        let last = self.cols - 1;
+        let line = &mut self.grid[row];
+        if line[last].width > 1 {
+            line[last] = Cell::blank(&self.attrs);
+        }
+    }
+
+    /// Erase cells `start..end` on `row` (clamped), splitting cut wide clusters.
+    fn erase_line(&mut self, row: usize, start: usize, end: usize) {
+        let end = end.min(self.cols);
+        if start >= end {
+            return;
+        }
+        self.split_wide(row, start);
+        self.split_wide(row, end);
+
+        let blank = Cell::blank(&self.attrs);
+        for cell in &mut self.grid[row][start..end] {
+            *cell = blank.clone();
+        }
+    }
+
+    fn carriage_return(&mut self) {
+        self.wrap_pending = false;
+        self.col = 0;
+    }
+
+    fn newline(&mut self) {
+        self.carriage_return();
+        self.index();
+    }
+
+    /// Move down one row, scrolling at the bottom.
+    fn index(&mut self) {
+        self.wrap_pending = false;
+        if self.row + 1 >= self.rows {
+            self.scroll_up(1);
+        } else {
+            self.row += 1;
+        }
+    }
+
+    /// Move up one row, scrolling at the top.
+    fn reverse_index(&mut self) {
+        self.wrap_pending = false;
+        if self.row == 0 {
+            self.scroll_down(1);
+        } else {
+            self.row -= 1;
+        }
+    }
+
+    fn scroll_up(&mut self, n: usize) {
+        for _ in 0..n.min(self.rows) {
+            self.grid.remove(0);
+            self.grid.push(vec![Cell::blank(&self.attrs); self.cols]);
+        }
+    }
+
+    fn scroll_down(&mut self, n: usize) {
+        for _ in 0..n.min(self.rows) {
+            self.grid.pop();
+            self.grid
+                .insert(0, vec![Cell::blank(&self.attrs); self.cols]);
+        }
+    }
+
+    fn restore_cursor(&mut self) {
+        let (row, col, attrs) = self.saved.clone();
+        self.row = row.min(self.rows - 1);
+        self.col = col.min(self.cols - 1);
+        self.attrs = attrs;
+        self.wrap_pending = false;
+    }
+}
@@ -6,16 +6,20 @@
 //!
 //! The context is passed into all command handlers to enable consistent behavior.
 
-use crate::config::settings::Config;
+use crate::config::settings::{Config, PolicySection};
 use crate::i18n::{keys::MessageKey, lang::Lang};
+use crate::style::theme::Theme;
 
 /// Global runtime context
 ///
-/// Holds resolved runtime configuration such as language,
-/// and provides helpers like internationalized message lookup.
+/// Holds resolved runtime configuration such as language, user-defined
+/// width policies and the highlight theme, and provides helpers like
+/// internationalized message lookup.
 #[derive(Debug, Clone)]
 pub struct Context {
     pub lang: Lang,
+    pub policies: Vec<PolicySection>,
+    pub theme: Theme,
 }
 
 impl Context {
@@ -24,7 +28,11 @@ impl Context {
     /// Falls back to defaults (e.g. `en-US`) if config file is missing or invalid.
     pub fn init() -> Self {
         let config = Config::load();
-        Context { lang: config.lang }
+        Context {
+            lang: config.lang,
+            policies: config.policies,
+            theme: Theme::from_entries(&config.theme),
+        }
     }
 
     /// Universal i18n resolver: supports any key type (e.g. `TitleKey`, `ErrorKey`)
@@ -2,4 +2,4 @@ mod context;
 mod settings;
 
 pub use context::Context;
-pub use settings::Config;
+pub use settings::{Config, PolicySection};
@@ -7,6 +7,27 @@
 //! # Supported Keys
 //! - `lang = zh-CN | en-US | ja-JP`
 //!
+//! # Policy Sections
+//! Named width policies are declared as `[policy.<name>]` sections, whose
+//! `key = value` entries are interpreted by `commands::policy`:
+//!
+//! ```text
+//! [policy.my-term]
+//! base = kitty
+//! ambiguous = 2
+//! U+E0A0..U+E0D4 = 1
+//! ```
+//!
+//! # Theme Section
+//! Highlight colors are set in a `[theme]` section, interpreted by
+//! `style::theme`:
+//!
+//! ```text
+//! [theme]
+//! zwj = bold magenta
+//! control = white on red
+//! ```
+//!
 //! # Fallback
 //! - If config file is missing or invalid, defaults to `en-US`.
 
@@ -14,48 +35,58 @@ use crate::i18n::lang::Lang;
 use std::fs;
 use std::path::PathBuf;
 
+/// A `[policy.<name>]` section with its raw entries, in file order.
+#[derive(Debug, Clone)]
+pub struct PolicySection {
+    pub name: String,
+    pub entries: Vec<(String, String)>,
+}
+
 #[derive(Debug)]
 pub struct Config {
     pub lang: Lang,
+    pub policies: Vec<PolicySection>,
+    pub theme: Vec<(String, String)>,
 }
 
 impl Config {
     /// Load configuration from platform-specific path.
     pub fn load() -> Self {
-        let path = Self::config_path();
-
-        let content = fs::read_to_string(&path).unwrap_or_default();
+        let content = Self::config_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .unwrap_or_default();
         let lang = Self::parse_lang(&content).unwrap_or(Lang::EnUS);
+        let policies = Self::parse_policies(&content);
+        let theme = Self::parse_theme(&content);
 
-        Config { lang }
+        Config {
+            lang,
+            policies,
+            theme,
+        }
     }
 
     /// Return the platform-specific config file path following XDG spec (Linux/macOS)
-    /// and %APPDATA% convention (Windows).
+    /// and %APPDATA% convention (Windows), or `None` if the environment doesn't
+    /// say where it is.
     ///
     /// Examples:
     /// - Linux/macOS: ~/.config/runefix/config or $XDG_CONFIG_HOME/runefix/config
     /// - Windows:     %APPDATA%\runefix\config
     #[cfg(not(target_os = "windows"))]
-    pub fn config_path() -> PathBuf {
+    pub fn config_path() -> Option<PathBuf> {
         if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from) {
-            dir.join("runefix").join("config")
-        } else if let Some(home) = std::env::var_os("HOME").map(PathBuf::from) {
-            home.join(".config").join("runefix").join("config")
+            Some(dir.join("runefix").join("config"))
         } else {
-            eprintln!("❌ Could not determine config path (missing $HOME or $XDG_CONFIG_HOME)");
-            std::process::exit(1);
+            let home = std::env::var_os("HOME").map(PathBuf::from)?;
+            Some(home.join(".config").join("runefix").join("config"))
         }
     }
 
     #[cfg(target_os = "windows")]
-    pub fn config_path() -> PathBuf {
-        if let Some(appdata) = std::env::var_os("APPDATA").map(PathBuf::from) {
-            appdata.join("runefix").join("config")
-        } else {
-            eprintln!("❌ Could not determine config path (missing %APPDATA%)");
-            std::process::exit(1);
-        }
+    pub fn config_path() -> Option<PathBuf> {
+        let appdata = std::env::var_os("APPDATA").map(PathBuf::from)?;
+        Some(appdata.join("runefix").join("config"))
     }
 
     fn parse_lang(content: &str) -> Option<Lang> {
@@ -65,6 +96,11 @@ impl Config {
                 continue;
             }
 
+            // Top-level keys end at the first section header
+            if line.starts_with('[') {
+                break;
+            }
+
             if let Some((key, value)) = line.split_once('=') {
                 if key.trim() == "lang" {
                     let lang_str = value.trim().trim_matches('"');
@@ -79,4 +115,71 @@ impl Config {
         }
         None
     }
+
+    /// Collects `[policy.<name>]` sections; other sections are ignored.
+    fn parse_policies(content: &str) -> Vec<PolicySection> {
+        let mut sections: Vec<PolicySection> = Vec::new();
+        let mut in_policy = false;
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                in_policy = match header.trim().strip_prefix("policy.") {
+                    Some(name) => {
+                        sections.push(PolicySection {
+                            name: name.trim().trim_matches('"').to_string(),
+                            entries: Vec::new(),
+                        });
+                        true
+                    }
+                    None => false,
+                };
+                continue;
+            }
+
+            if let (true, Some((key, value)), Some(section)) =
+                (in_policy, line.split_once('='), sections.last_mut())
+            {
+                let value = value.trim().trim_matches('"');
+                section
+                    .entries
+                    .push((key.trim().to_string(), value.to_string()));
+            }
+        }
+
+        sections
+    }
+
+    /// Collects the entries of the `[theme]` section, in file order.
+    fn parse_theme(content: &str) -> Vec<(String, String)> {
+        let mut entries = Vec::new();
+        let mut in_theme = false;
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                in_theme = header.trim() == "theme";
+                continue;
+            }
+
+            if let (true, Some((key, value))) = (in_theme, line.split_once('=')) {
+                // Allow trailing comments, since styles never contain `#`
+                let value = value.split('#').next().unwrap_or_default();
+                entries.push((
+                    key.trim().to_string(),
+                    value.trim().trim_matches('"').to_string(),
+                ));
+            }
+        }
+
+        entries
+    }
 }
@@ -0,0 +1,146 @@
+//! Typed errors with stable codes and exit statuses.
+//!
+//! Every failure runefix detects itself is a [`RunefixError`]: an [`ErrorKey`]
+//! (which fixes its stable `RFxxxx` code and [`ErrorClass`]) plus the message
+//! localized for the current language. [`report`] prints any error reaching
+//! `main` and picks the exit status:
+//!
+//! | Exit | Class        | Meaning                                                  |
+//! |------|--------------|----------------------------------------------------------|
+//! | 0    |              | Success (also when the reader of a pipe goes away)       |
+//! | 1    |              | Unexpected internal error                                |
+//! | 2    | `Usage`      | Invalid arguments or option combinations (as clap's own) |
+//! | 3    | `Input`      | Input text missing or unreadable                         |
+//! | 4    | `Constraint` | Input violates a requested constraint (`--strict`)       |
+//! | 5    | `Io`         | Files, output or the terminal could not be used          |
+//!
+//! Codes are grouped by area: `RF00xx` options, `RF01xx` slicing, `RF02xx`
+//! input, `RF03xx` I/O. A code never changes meaning once released.
+
+use crate::config::Context;
+use crate::i18n::format_i18n;
+use crate::i18n::keys::ErrorKey;
+use std::fmt;
+use std::io;
+use std::process::ExitCode;
+
+/// Category of an error, which decides the exit status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// Invalid arguments or option combinations
+    Usage,
+
+    /// Input text missing or unreadable
+    Input,
+
+    /// Input violates a requested constraint
+    Constraint,
+
+    /// Files, output or the terminal could not be used
+    Io,
+}
+
+impl ErrorClass {
+    /// Process exit status for this class.
+    pub fn exit_code(self) -> u8 {
+        match self {
+            ErrorClass::Usage => 2,
+            ErrorClass::Input => 3,
+            ErrorClass::Constraint => 4,
+            ErrorClass::Io => 5,
+        }
+    }
+}
+
+/// Returns the stable code and class of an error key.
+fn describe(key: ErrorKey) -> (&'static str, ErrorClass) {
+    use ErrorClass::*;
+    use ErrorKey::*;
+    match key {
+        FormatUnsupported => ("RF0001", Usage),
+        WidthRequired => ("RF0002", Usage),
+        PolicyUnknown => ("RF0003", Usage),
+        PolicyDiffTooFew => ("RF0004", Usage),
+        TabStopInvalid => ("RF0005", Usage),
+        OverrideInvalid => ("RF0006", Usage),
+        CompareLibcUnavailable => ("RF0007", Usage),
+        SliceTrim | SliceBrackets | SliceSpaces | SliceFormat => ("RF0101", Usage),
+        SliceParseSingle | SliceParseStart | SliceParseEnd => ("RF0102", Usage),
+        SliceOutOfBounds => ("RF0103", Constraint),
+        SliceWidthUnaligned => ("RF0104", Constraint),
+        InputTextNoProvided => ("RF0201", Input),
+        InputUnreadable => ("RF0202", Input),
+        OverrideFileUnreadable => ("RF0301", Io),
+        ConfigPathUnknown => ("RF0302", Io),
+        ProbeNoTerminal => ("RF0303", Io),
+        ProbeNoReply => ("RF0304", Io),
+        IoFailed => ("RF0305", Io),
+    }
+}
+
+/// An error with a stable code and a localized message.
+#[derive(Debug)]
+pub struct RunefixError {
+    key: ErrorKey,
+    message: String,
+}
+
+impl RunefixError {
+    /// Creates the error for `key`, localized for `ctx`.
+    pub fn new(ctx: &Context, key: ErrorKey) -> Self {
+        RunefixError {
+            message: ctx.t(key),
+            key,
+        }
+    }
+
+    /// Creates the error for `key`, filling `{placeholders}` from `args`.
+    pub fn with_args(ctx: &Context, key: ErrorKey, args: &[(&str, String)]) -> Self {
+        RunefixError {
+            message: format_i18n(&ctx.t(key), args),
+            key,
+        }
+    }
+
+    /// Stable error code (e.g. `RF0103`).
+    pub fn code(&self) -> &'static str {
+        describe(self.key).0
+    }
+
+    /// Class of the error, which decides the exit status.
+    pub fn class(&self) -> ErrorClass {
+        describe(self.key).1
+    }
+}
+
+impl fmt::Display for RunefixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for RunefixError {}
+
+/// Prints `err` to stderr with its code and returns the matching exit status.
+///
+/// I/O errors without a runefix code are reported as `RF0305`; anything else
+/// is an internal error (exit 1).
+pub fn report(ctx: &Context, err: &anyhow::Error) -> ExitCode {
+    let known = err.chain().find_map(|e| e.downcast_ref::<RunefixError>());
+    let io = err.chain().find_map(|e| e.downcast_ref::<io::Error>());
+
+    let (message, code, status) = match (known, io) {
+        (Some(e), _) => (e.to_string(), e.code(), e.class().exit_code()),
+        (None, Some(io)) => {
+            let e = RunefixError::with_args(ctx, ErrorKey::IoFailed, &[("error", io.to_string())]);
+            (e.to_string(), e.code(), e.class().exit_code())
+        }
+        (None, None) => {
+            eprintln!("❌ {err:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    eprintln!("{message} [{code}]");
+    ExitCode::from(status)
+}
@@ -9,7 +9,7 @@ use std::hash::Hash;
 
 /// Keys for localized error messages.
 #[allow(clippy::enum_variant_names)]
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ErrorKey {
     InputTextNoProvided,
     SliceTrim,
@@ -19,9 +19,21 @@ pub enum ErrorKey {
     SliceParseSingle,
     SliceParseStart,
     SliceParseEnd,
-    SliceExprFallback,
     SliceOutOfBounds,
     SliceWidthUnaligned,
+    TabStopInvalid,
+    ProbeNoTerminal,
+    ProbeNoReply,
+    OverrideInvalid,
+    OverrideFileUnreadable,
+    PolicyDiffTooFew,
+    CompareLibcUnavailable,
+    FormatUnsupported,
+    WidthRequired,
+    PolicyUnknown,
+    InputUnreadable,
+    ConfigPathUnknown,
+    IoFailed,
 }
 
 /// Keys for command titles or section headers.
@@ -35,6 +47,13 @@ pub enum TitleKey {
     WidthPerChar,
     RunefixVersion,
     SplitLines,
+    ColumnRuler,
+    CellGrid,
+    TerminalProbe,
+    TestPattern,
+    PolicyDiff,
+    CompareLibc,
+    UnicodeDiff,
 }
 
 /// Keys for footers, legends, or result annotations.
@@ -43,6 +62,12 @@ pub enum FooterKey {
     AtomsDetail,
     LegendGlossary,
     SliceSummary,
+    SliceOutOfRange,
+    ProbeMismatches,
+    ProbeBestMatch,
+    PolicyDiffTotal,
+    PolicyDiffSummary,
+    UnicodeDiffSummary,
 }
 
 /// Keys used for reporting diagnostic messages in CLI output.
@@ -17,9 +17,21 @@ pub fn error_en_us() -> HashMap<ErrorKey, &'static str> {
         (SliceParseSingle, "❌ slice index must be a non-negative integer (e.g. [0], [3])"),
         (SliceParseStart, "❌ start index must be a non-negative integer"),
         (SliceParseEnd, "❌ end index must be a non-negative integer"),
-        (SliceExprFallback, "❌ failed to parse slice expression"),
         (SliceOutOfBounds, "❌ slice range out of bounds (len = {len})"),
         (SliceWidthUnaligned, "❌ width slice must align with visual cell boundaries\nvalid boundaries: {boundaries}"),
+        (TabStopInvalid, "❌ tab stops must be a positive integer up to 1024 or an ascending comma-separated list (e.g. 4 or 4,8,12)"),
+        (ProbeNoTerminal, "❌ no controlling terminal available (use --emulate <POLICY> to probe a virtual one)"),
+        (ProbeNoReply, "❌ terminal did not answer the cursor position query (CSI 6n)"),
+        (OverrideInvalid, "❌ invalid width override '{spec}' (expected e.g. U+E0B0=1, U+E0A0..U+E0D4=2, or nerd-fonts)"),
+        (OverrideFileUnreadable, "❌ cannot read override file \"{path}\""),
+        (PolicyDiffTooFew, "❌ policy diff needs at least two known policies (e.g. terminal markdown)"),
+        (CompareLibcUnavailable, "❌ libc width comparison is only available on Unix-like systems"),
+        (FormatUnsupported, "❌ --format {format} is not supported by this command (json/ndjson: atoms, graphemes, width, widths, split, truncate, slice, screen, compare, version; csv/tsv: atoms, graphemes, widths; html/svg: atoms, graphemes; c/rust/go/python/json: export-table)"),
+        (WidthRequired, "❌ --width is required when output is not a terminal (use a column count, auto or -N)"),
+        (PolicyUnknown, "❌ unknown policy '{name}' (expected terminal, markdown, compact, an emulator profile, auto, or a [policy.<name>] config section)"),
+        (InputUnreadable, "❌ cannot read input from stdin: {error}"),
+        (ConfigPathUnknown, "❌ could not determine the config path (set $XDG_CONFIG_HOME or $HOME; %APPDATA% on Windows)"),
+        (IoFailed, "❌ I/O error: {error}"),
     ])
 }
 
@@ -35,6 +47,13 @@ pub fn title_en_us() -> HashMap<TitleKey, &'static str> {
         (WidthPerChar, "📐 Width Per Character"),
         (RunefixVersion, "🪪 Runefix Version Info"),
         (SplitLines, "🪓 Split Lines"),
+        (ColumnRuler, "📏 Column Ruler"),
+        (CellGrid, "🔲 Cell Occupancy"),
+        (TerminalProbe, "🔬 Terminal Probe"),
+        (TestPattern, "🎯 Terminal Test Pattern"),
+        (PolicyDiff, "⚖️ Policy Diff"),
+        (CompareLibc, "🆚 Runefix vs libc wcwidth"),
+        (UnicodeDiff, "🆕 Unicode Version Diff"),
     ])
 }
 
@@ -45,6 +64,12 @@ pub fn footer_en_us() -> HashMap<FooterKey, &'static str> {
         (AtomsDetail, "Total Display Width"),
         (LegendGlossary, "📘 Legend"),
         (SliceSummary, "Total units: {total}, Range: [{start}..{end}]"),
+        (SliceOutOfRange, "⚠ {expr} runs past the end of the line ({total}); the result is clamped"),
+        (ProbeMismatches, "Mismatches"),
+        (ProbeBestMatch, "Best matching policy"),
+        (PolicyDiffTotal, "Total"),
+        (PolicyDiffSummary, "{diff} of {total} graphemes differ"),
+        (UnicodeDiffSummary, "{count} code points in {ranges} ranges change width between Unicode {from} and {to}"),
     ])
 }
 
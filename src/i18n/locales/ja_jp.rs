@@ -17,9 +17,21 @@ pub fn error_ja_jp() -> HashMap<ErrorKey, &'static str> {
         (SliceParseSingle, "❌ 単一のインデックスは非負整数でなければなりません（例: [0], [3]）"),
         (SliceParseStart, "❌ 開始インデックスは非負整数でなければなりません"),
         (SliceParseEnd, "❌ 終了インデックスは非負整数でなければなりません"),
-        (SliceExprFallback, "❌ スライス式の解析に失敗しました"),
         (SliceOutOfBounds, "❌ スライス範囲が無効です（長さ = {len}）"),
         (SliceWidthUnaligned, "❌ 幅モードではスライスは表示セルの境界に揃える必要があります\n有効な境界: {boundaries}"),
+        (TabStopInvalid, "❌ タブ位置は 1024 以下の正の整数、または昇順のカンマ区切りリストで指定してください（例: 4 または 4,8,12）"),
+        (ProbeNoTerminal, "❌ 制御端末が利用できません（--emulate <POLICY> で仮想端末を調査できます）"),
+        (ProbeNoReply, "❌ 端末がカーソル位置の問い合わせ（CSI 6n）に応答しませんでした"),
+        (OverrideInvalid, "❌ 無効な幅オーバーライド '{spec}'（例: U+E0B0=1、U+E0A0..U+E0D4=2、nerd-fonts）"),
+        (OverrideFileUnreadable, "❌ オーバーライドファイルを読み込めません \"{path}\""),
+        (PolicyDiffTooFew, "❌ policy diff には既知のポリシーが 2 つ以上必要です（例: terminal markdown）"),
+        (CompareLibcUnavailable, "❌ libc との幅比較は Unix 系システムでのみ利用できます"),
+        (FormatUnsupported, "❌ このコマンドは --format {format} に対応していません（json/ndjson: atoms、graphemes、width、widths、split、truncate、slice、screen、compare、version / csv/tsv: atoms、graphemes、widths / html/svg: atoms、graphemes / c/rust/go/python/json: export-table）"),
+        (WidthRequired, "❌ 出力が端末でない場合は --width が必要です（列数、auto、または -N）"),
+        (PolicyUnknown, "❌ 不明なポリシー '{name}'（terminal、markdown、compact、エミュレータプロファイル、auto、または設定ファイルの [policy.<name>] セクションを指定してください）"),
+        (InputUnreadable, "❌ 標準入力を読み取れません：{error}"),
+        (ConfigPathUnknown, "❌ 設定ファイルのパスを特定できません（$XDG_CONFIG_HOME または $HOME を設定してください。Windows では %APPDATA%）"),
+        (IoFailed, "❌ I/O エラー：{error}"),
     ])
 }
 
@@ -35,6 +47,13 @@ pub fn title_ja_jp() -> HashMap<TitleKey, &'static str> {
         (WidthPerChar, "📐 文字ごとの幅"),
         (RunefixVersion, "🪪 Runefix バージョン情報"),
         (SplitLines, "🪓 行の分割"),
+        (ColumnRuler, "📏 カラムルーラー"),
+        (CellGrid, "🔲 セル占有"),
+        (TerminalProbe, "🔬 端末プローブ"),
+        (TestPattern, "🎯 端末テストパターン"),
+        (PolicyDiff, "⚖️ ポリシー差分"),
+        (CompareLibc, "🆚 Runefix と libc wcwidth の比較"),
+        (UnicodeDiff, "🆕 Unicode バージョン差分"),
     ])
 }
 
@@ -45,6 +64,12 @@ pub fn footer_ja_jp() -> HashMap<FooterKey, &'static str> {
         (AtomsDetail, "表示幅合計"),
         (LegendGlossary, "📘 凡例"),
         (SliceSummary, "合計ユニット数: {total}、範囲: [{start}..{end}]"),
+        (SliceOutOfRange, "⚠ {expr} は行末（{total}）を超えています。結果は切り詰められました"),
+        (ProbeMismatches, "不一致数"),
+        (ProbeBestMatch, "最も一致するポリシー"),
+        (PolicyDiffTotal, "合計"),
+        (PolicyDiffSummary, "{total} 個の書記素のうち {diff} 個の幅が異なります"),
+        (UnicodeDiffSummary, "Unicode {from} と {to} の間で {count} 個のコードポイント（{ranges} 範囲）の幅が異なります"),
     ])
 }
 
@@ -17,9 +17,21 @@ pub fn error_zh_cn() -> HashMap<ErrorKey, &'static str> {
         (SliceParseSingle, "❌ 切片索引必须是非负整数（如 [0] 或 [3]）"),
         (SliceParseStart, "❌ 起始索引必须是非负整数"),
         (SliceParseEnd, "❌ 结束索引必须是非负整数"),
-        (SliceExprFallback, "❌ 解析切片表达式失败"),
         (SliceOutOfBounds, "❌ 切片索引超出范围（总长度 = {len}）"),
         (SliceWidthUnaligned, "❌ width 模式下切片必须对齐显示单元边界\n合法边界值包括：{boundaries}"),
+        (TabStopInvalid, "❌ 制表位必须是不超过 1024 的正整数或递增的逗号分隔列表（如 4 或 4,8,12）"),
+        (ProbeNoTerminal, "❌ 没有可用的控制终端（可使用 --emulate <POLICY> 探测虚拟终端）"),
+        (ProbeNoReply, "❌ 终端未响应光标位置查询（CSI 6n）"),
+        (OverrideInvalid, "❌ 无效的宽度覆盖 '{spec}'（应为 U+E0B0=1、U+E0A0..U+E0D4=2 或 nerd-fonts 等）"),
+        (OverrideFileUnreadable, "❌ 无法读取覆盖文件 \"{path}\""),
+        (PolicyDiffTooFew, "❌ policy diff 至少需要两个已知策略（例如 terminal markdown）"),
+        (CompareLibcUnavailable, "❌ libc 宽度对比仅支持类 Unix 系统"),
+        (FormatUnsupported, "❌ 此命令不支持 --format {format}（json/ndjson：atoms、graphemes、width、widths、split、truncate、slice、screen、compare、version；csv/tsv：atoms、graphemes、widths；html/svg：atoms、graphemes；c/rust/go/python/json：export-table）"),
+        (WidthRequired, "❌ 输出不是终端时必须指定 --width（列数、auto 或 -N）"),
+        (PolicyUnknown, "❌ 未知策略 '{name}'（可用 terminal、markdown、compact、终端模拟器配置、auto，或配置文件中的 [policy.<name>] 段）"),
+        (InputUnreadable, "❌ 无法从标准输入读取：{error}"),
+        (ConfigPathUnknown, "❌ 无法确定配置文件路径（请设置 $XDG_CONFIG_HOME 或 $HOME；Windows 上为 %APPDATA%）"),
+        (IoFailed, "❌ I/O 错误：{error}"),
     ])
 }
 
@@ -35,6 +47,13 @@ pub fn title_zh_cn() -> HashMap<TitleKey, &'static str> {
         (WidthPerChar, "📐 单字符宽度"),
         (RunefixVersion, "🪪 Runefix 版本信息"),
         (SplitLines, "🪓 拆分行"),
+        (ColumnRuler, "📏 列标尺"),
+        (CellGrid, "🔲 单元格占用"),
+        (TerminalProbe, "🔬 终端探测"),
+        (TestPattern, "🎯 终端测试图案"),
+        (PolicyDiff, "⚖️ 策略差异"),
+        (CompareLibc, "🆚 Runefix 与 libc wcwidth 对比"),
+        (UnicodeDiff, "🆕 Unicode 版本差异"),
     ])
 }
 
@@ -45,6 +64,12 @@ pub fn footer_zh_cn() -> HashMap<FooterKey, &'static str> {
         (AtomsDetail, "总显示宽度"),
         (LegendGlossary, "📘 图例"),
         (SliceSummary, "总单元数：{total}，切片区间：[{start}..{end}]"),
+        (SliceOutOfRange, "⚠ {expr} 超出行尾（{total}），结果已截断"),
+        (ProbeMismatches, "不一致数"),
+        (ProbeBestMatch, "最匹配的策略"),
+        (PolicyDiffTotal, "合计"),
+        (PolicyDiffSummary, "{total} 个字素中有 {diff} 个宽度不同"),
+        (UnicodeDiffSummary, "Unicode {from} 与 {to} 之间有 {count} 个码位（{ranges} 个区间）宽度不同"),
     ])
 }
 
@@ -5,25 +5,44 @@
 
 mod commands;
 mod config;
+mod error;
 mod i18n;
 mod style;
 
-use anyhow::Result;
 use clap::Parser;
 use commands::cli::Cli;
 use commands::dispatch::dispatch;
 use config::Context;
+use error::report;
+use std::io::Write;
+use std::process::ExitCode;
+use style::term::set_color_choice;
+use style::writer::{StdoutWriter, is_broken_pipe};
 
 /// Main entry point for the CLI.
 ///
 /// Initializes runtime context, parses arguments, and routes commands.
-fn main() -> Result<()> {
+/// Errors are printed with their code and exit with the status of their
+/// class (see [`error`]).
+fn main() -> ExitCode {
     // Initialize global config context (e.g. language, theme)
     let ctx = Context::init();
 
     // Parse CLI arguments using Clap
     let cli = Cli::parse();
 
-    // Dispatch to the appropriate command handler
-    dispatch(ctx, cli)
+    // Resolve `--color` (and NO_COLOR / CLICOLOR_FORCE) before any output
+    set_color_choice(cli.color);
+
+    // Dispatch to the appropriate command handler, writing through buffered stdout
+    let mut out = StdoutWriter::new();
+    let result = dispatch(&ctx, cli, &mut out);
+    let result = result.and(out.flush().map_err(Into::into));
+
+    // A reader that went away (e.g. `| head`) ends the run quietly
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) if is_broken_pipe(&err) => ExitCode::SUCCESS,
+        Err(err) => report(&ctx, &err),
+    }
 }
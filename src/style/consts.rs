@@ -1,4 +0,0 @@
-//! Constants used throughout the `runefix` CLI.
-
-/// Default width for horizontal divider lines in CLI output
-pub const WIDTH_LINE: usize = 55;
@@ -0,0 +1,167 @@
+//! Box-drawing helpers for framed banners and divider lines.
+//!
+//! All measurements go through `runefix-core`, so CJK and emoji content
+//! keeps the right border aligned regardless of the chosen `LayoutPolicy`.
+
+use crate::commands::policy::LayoutPolicy;
+use clap::ValueEnum;
+
+/// Border style used when drawing frames.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum BorderStyle {
+    // ┌─┐ light box-drawing lines
+    #[default]
+    Single,
+
+    // ╔═╗ double lines
+    Double,
+
+    // ╭─╮ light lines with rounded corners
+    Rounded,
+
+    // ┏━┓ heavy lines
+    Heavy,
+
+    // +-+ plain ASCII fallback
+    Ascii,
+}
+
+/// Glyph set for a single border style.
+pub struct BorderChars {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+}
+
+impl BorderStyle {
+    /// Returns the glyphs used to draw this style.
+    pub fn chars(self) -> BorderChars {
+        let (tl, tr, bl, br, h, v) = match self {
+            BorderStyle::Single => ('┌', '┐', '└', '┘', '─', '│'),
+            BorderStyle::Double => ('╔', '╗', '╚', '╝', '═', '║'),
+            BorderStyle::Rounded => ('╭', '╮', '╰', '╯', '─', '│'),
+            BorderStyle::Heavy => ('┏', '┓', '┗', '┛', '━', '┃'),
+            BorderStyle::Ascii => ('+', '+', '+', '+', '-', '|'),
+        };
+
+        BorderChars {
+            top_left: tl,
+            top_right: tr,
+            bottom_left: bl,
+            bottom_right: br,
+            horizontal: h,
+            vertical: v,
+        }
+    }
+}
+
+/// Horizontal alignment of content inside a frame.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Layout options for [`render_frame`].
+pub struct FrameOptions<'a> {
+    pub style: BorderStyle,
+    pub title: Option<&'a str>,
+    pub padding: usize,
+    pub align: Align,
+    pub policy: &'a LayoutPolicy,
+}
+
+/// Returns a horizontal rule of `width` columns in the given style.
+pub fn horizontal_rule(style: BorderStyle, width: usize) -> String {
+    style.chars().horizontal.to_string().repeat(width)
+}
+
+/// Pads `text` to `width` display columns according to `align`.
+///
+/// Text wider than `width` is returned unchanged.
+pub fn align_text(text: &str, width: usize, align: Align, policy: &LayoutPolicy) -> String {
+    let w = policy.display_width(text);
+    let gap = width.saturating_sub(w);
+
+    let (left, right) = match align {
+        Align::Left => (0, gap),
+        Align::Center => (gap / 2, gap - gap / 2),
+        Align::Right => (gap, 0),
+    };
+
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
+
+/// Draws a frame around `lines`, returning the rendered rows.
+///
+/// The inner width is the widest line plus padding on both sides, grown if
+/// necessary so the title fits into the top border as `─ Title ─`.
+pub fn render_frame(lines: &[&str], opts: &FrameOptions) -> Vec<String> {
+    let c = opts.style.chars();
+    let policy = opts.policy;
+
+    let content_width = lines
+        .iter()
+        .map(|l| policy.display_width(l))
+        .max()
+        .unwrap_or(0);
+
+    // Title is rendered as `─ Title ─`, so it needs 4 extra columns
+    let title_width = opts.title.map(|t| policy.display_width(t) + 4).unwrap_or(0);
+
+    let padding = opts.padding.saturating_mul(2);
+    let inner = content_width.saturating_add(padding).max(title_width);
+    let text_width = inner.saturating_sub(padding);
+
+    let mut rows = Vec::with_capacity(lines.len() + 2);
+
+    // Top border, with optional embedded title
+    let top = match opts.title {
+        Some(t) => {
+            let used = policy.display_width(t) + 3;
+            format!(
+                "{}{} {} {}{}",
+                c.top_left,
+                c.horizontal,
+                t,
+                horizontal_rule(opts.style, inner - used),
+                c.top_right
+            )
+        }
+        None => format!(
+            "{}{}{}",
+            c.top_left,
+            horizontal_rule(opts.style, inner),
+            c.top_right
+        ),
+    };
+    rows.push(top);
+
+    // Content rows
+    let pad = " ".repeat(opts.padding);
+    for line in lines {
+        rows.push(format!(
+            "{}{}{}{}{}",
+            c.vertical,
+            pad,
+            align_text(line, text_width, opts.align, opts.policy),
+            pad,
+            c.vertical
+        ));
+    }
+
+    // Bottom border
+    rows.push(format!(
+        "{}{}{}",
+        c.bottom_left,
+        horizontal_rule(opts.style, inner),
+        c.bottom_right
+    ));
+
+    rows
+}
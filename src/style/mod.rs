@@ -1,3 +1,6 @@
-pub mod consts;
+pub mod frame;
 pub mod print;
+pub mod table;
 pub mod term;
+pub mod theme;
+pub mod writer;
@@ -3,27 +3,37 @@
 //! This module provides reusable utilities to print titles,
 //! apply styling, and maintain visual consistency across commands.
 
-use crate::style::{consts::WIDTH_LINE, term::is_stdout_terminal};
+use crate::commands::policy::LayoutPolicy;
+use crate::style::{
+    frame::{BorderStyle, horizontal_rule},
+    term::{bold, terminal_columns, use_color},
+};
+use std::io::{self, Write};
 
-/// Prints a styled section title with a horizontal divider.
+/// Writes a styled section title with a horizontal divider to `out`.
 ///
 /// Example output (when stdout is a terminal):
 /// ```
 /// 📏 Display Width
-/// ─────────────────────────────────────────
+/// ───────────────
 /// ```
 ///
-/// - Applies bold styling if output is a terminal
+/// - Applies bold styling if color is enabled (see `--color`)
 /// - Falls back to plain text otherwise
-pub fn print_title(label: &str) {
-    if is_stdout_terminal() {
-        // Bold output for TTY environments
-        println!("\n\x1b[1m{label}\x1b[0m");
+/// - The divider spans the title, like the content-sized table rules, capped at
+///   the terminal width
+pub fn print_title(out: &mut dyn Write, label: &str) -> io::Result<()> {
+    if use_color() {
+        // Bold output for color-enabled environments
+        writeln!(out, "\n{}", bold(label))?;
     } else {
-        // Plain output for non-TTY environments
-        println!("{label}");
+        // Plain output otherwise
+        writeln!(out, "{label}")?;
     }
 
     // Print horizontal divider line
-    println!("{}", "─".repeat(WIDTH_LINE));
+    let width = LayoutPolicy::terminal()
+        .display_width(label)
+        .min(terminal_columns().unwrap_or(usize::MAX));
+    writeln!(out, "{}", horizontal_rule(BorderStyle::Single, width))
 }
@@ -0,0 +1,180 @@
+//! Width-aware table renderer shared by the table-printing commands.
+//!
+//! Every cell, including localized (CJK) headers and labels, is measured with
+//! `runefix-core` through the terminal policy, so columns line up whatever the
+//! UI language or content. Columns are as wide as their widest cell, and
+//! dividers span the table, capped at the terminal width.
+//!
+//! ```text
+//!  Grapheme  libc  terminal  Code Points
+//!  ─────────────────────────────────────
+//!  ✅        1     2 ✗       U+2705
+//!  ─────────────────────────────────────
+//!  Mismatches      1
+//! ```
+
+use crate::commands::policy::LayoutPolicy;
+use crate::style::frame::{Align, BorderStyle, align_text, horizontal_rule};
+use crate::style::term::{bold, terminal_columns};
+use crate::style::theme::{Role, Theme};
+use std::io::{self, Write};
+
+/// Blank columns between adjacent columns.
+const GAP: usize = 2;
+
+/// One line of the table body.
+enum Row {
+    /// Regular cells, optionally highlighted by layout role
+    Cells(Vec<String>, Option<Role>),
+
+    /// A label spanning the first `span` columns, then regular cells
+    Span(String, usize, Vec<String>),
+
+    /// A horizontal divider
+    Divider,
+}
+
+/// A table with a header row, body rows and dividers.
+pub struct Table {
+    headers: Vec<String>,
+    aligns: Vec<Align>,
+    rows: Vec<Row>,
+}
+
+impl Table {
+    /// Creates a table with the given column headers, all left-aligned.
+    pub fn new<S: Into<String>>(headers: impl IntoIterator<Item = S>) -> Self {
+        let headers: Vec<String> = headers.into_iter().map(Into::into).collect();
+        let aligns = vec![Align::Left; headers.len()];
+        Table {
+            headers,
+            aligns,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Sets the alignment of column `col`.
+    pub fn align(mut self, col: usize, align: Align) -> Self {
+        if let Some(a) = self.aligns.get_mut(col) {
+            *a = align;
+        }
+        self
+    }
+
+    /// Appends a row of cells.
+    pub fn row(&mut self, cells: Vec<String>) {
+        self.rows.push(Row::Cells(cells, None));
+    }
+
+    /// Appends a row highlighted in the theme style of `role`, if any.
+    pub fn styled_row(&mut self, cells: Vec<String>, role: Option<Role>) {
+        self.rows.push(Row::Cells(cells, role));
+    }
+
+    /// Appends a row whose `label` spans the first `span` columns, followed
+    /// by `cells` for the remaining columns (e.g. a totals row).
+    pub fn span_row(&mut self, label: String, span: usize, cells: Vec<String>) {
+        self.rows.push(Row::Span(label, span.max(1), cells));
+    }
+
+    /// Appends a horizontal divider.
+    pub fn divider(&mut self) {
+        self.rows.push(Row::Divider);
+    }
+
+    /// Computes the display width of each column.
+    fn column_widths(&self, policy: &LayoutPolicy) -> Vec<usize> {
+        let mut widths: Vec<usize> = self
+            .headers
+            .iter()
+            .map(|h| policy.display_width(h))
+            .collect();
+        let mut fit = |col: usize, cell: &str| {
+            if let Some(w) = widths.get_mut(col) {
+                *w = (*w).max(policy.display_width(cell));
+            }
+        };
+
+        let mut spans = Vec::new();
+        for row in &self.rows {
+            match row {
+                Row::Cells(cells, _) => cells.iter().enumerate().for_each(|(i, c)| fit(i, c)),
+                Row::Span(label, span, cells) => {
+                    cells.iter().enumerate().for_each(|(i, c)| fit(span + i, c));
+                    spans.push((label, *span));
+                }
+                Row::Divider => {}
+            }
+        }
+
+        // Grow the last spanned column if a label doesn't fit its span
+        for (label, span) in spans {
+            let span = span.min(widths.len());
+            if span == 0 {
+                continue;
+            }
+            let available = widths[..span].iter().sum::<usize>() + GAP * (span - 1);
+            let needed = policy.display_width(label);
+            if needed > available {
+                widths[span - 1] += needed - available;
+            }
+        }
+
+        widths
+    }
+
+    /// Renders the table into lines: a bold header, a divider, then the body.
+    pub fn render(&self, theme: &Theme) -> Vec<String> {
+        let policy = LayoutPolicy::terminal();
+        let widths = self.column_widths(&policy);
+
+        let table_width = widths.iter().sum::<usize>() + GAP * widths.len().saturating_sub(1);
+        let rule_width = (table_width + 1).min(terminal_columns().unwrap_or(usize::MAX));
+        let rule = horizontal_rule(BorderStyle::Single, rule_width);
+
+        let join = |cells: &[String], first_col: usize, lead: Option<String>| {
+            let mut parts: Vec<String> = lead.into_iter().collect();
+            for (i, cell) in cells.iter().enumerate() {
+                let col = first_col + i;
+                let width = widths.get(col).copied().unwrap_or(0);
+                let align = self.aligns.get(col).copied().unwrap_or_default();
+                parts.push(align_text(cell, width, align, &policy));
+            }
+            parts.join(&" ".repeat(GAP)).trim_end().to_string()
+        };
+
+        let header = bold(&join(&self.headers, 0, None));
+        let mut lines = vec![format!(" {header}"), rule.clone()];
+        for row in &self.rows {
+            let line = match row {
+                Row::Cells(cells, role) => {
+                    let line = join(cells, 0, None);
+                    match role {
+                        Some(role) => theme.paint(*role, &line),
+                        None => line,
+                    }
+                }
+                Row::Span(label, span, cells) => {
+                    let span = (*span).min(widths.len());
+                    let width = widths[..span].iter().sum::<usize>() + GAP * span.saturating_sub(1);
+                    let lead = align_text(label, width, Align::Left, &policy);
+                    join(cells, span, Some(lead))
+                }
+                Row::Divider => {
+                    lines.push(rule.clone());
+                    continue;
+                }
+            };
+            lines.push(format!(" {line}"));
+        }
+        lines
+    }
+
+    /// Writes the table to `out`.
+    pub fn print(&self, out: &mut dyn Write, theme: &Theme) -> io::Result<()> {
+        for line in self.render(theme) {
+            writeln!(out, "{line}")?;
+        }
+        Ok(())
+    }
+}
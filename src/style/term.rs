@@ -1,4 +1,57 @@
 //! Terminal utilities for checking stream types and styling output.
+//!
+//! All ANSI styling goes through [`bold`] and [`paint`], which emit escape
+//! sequences only when [`use_color`] allows it (see [`set_color_choice`]).
+
+use clap::ValueEnum;
+use std::env;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// When to emit ANSI styling, selected with `--color`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal, unless `NO_COLOR` is set or `CLICOLOR_FORCE` forces it
+    #[default]
+    Auto,
+
+    /// Always color, even when piped (e.g. into `less -R`)
+    Always,
+
+    /// Never color
+    Never,
+}
+
+/// Whether styling is enabled, resolved once from `--color` and the environment.
+static COLOR: OnceLock<bool> = OnceLock::new();
+
+/// Resolves `choice` against the environment.
+///
+/// With `auto`, a non-empty `NO_COLOR` disables color, then a `CLICOLOR_FORCE`
+/// other than `0` enables it; otherwise color follows whether stdout is a TTY.
+fn resolve(choice: ColorChoice) -> bool {
+    let var = |name: &str| env::var_os(name).filter(|v| !v.is_empty());
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto if var("NO_COLOR").is_some() => false,
+        ColorChoice::Auto if var("CLICOLOR_FORCE").is_some_and(|v| v != "0") => true,
+        ColorChoice::Auto => is_stdout_terminal(),
+    }
+}
+
+/// Enables or disables styling for the rest of the process.
+/// Only the first call (or first styled output) takes effect.
+pub fn set_color_choice(choice: ColorChoice) {
+    let _ = COLOR.set(resolve(choice));
+}
+
+/// Returns `true` if ANSI styling should be emitted.
+///
+/// Falls back to `auto` if [`set_color_choice`] was never called.
+pub fn use_color() -> bool {
+    *COLOR.get_or_init(|| resolve(ColorChoice::Auto))
+}
 
 /// Returns `true` if stdout is a terminal (TTY).
 ///
@@ -16,7 +69,86 @@ pub fn is_stdin_terminal() -> bool {
 
 /// Wraps the given string in ANSI escape codes for bold styling.
 ///
-/// Returns the styled string, only meaningful if printed to a TTY.
+/// Returns the string unchanged when color is disabled.
 pub fn bold(s: &str) -> String {
-    format!("\x1b[1m{s}\x1b[0m")
+    paint(s, "1")
+}
+
+/// Wraps the given string in an arbitrary SGR sequence (e.g. `"44"` for a blue background).
+///
+/// Returns the string unchanged when color is disabled.
+pub fn paint(s: &str, sgr: &str) -> String {
+    if use_color() {
+        format!("\x1b[{sgr}m{s}\x1b[0m")
+    } else {
+        s.to_string()
+    }
+}
+
+/// Returns the width of the terminal in columns, if known.
+///
+/// Asks the terminal when stdout is a TTY (on Unix), then falls back to the
+/// `COLUMNS` environment variable.
+pub fn terminal_columns() -> Option<usize> {
+    #[cfg(unix)]
+    if is_stdout_terminal() {
+        // SAFETY: TIOCGWINSZ only writes into the zeroed `winsize` we pass
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+        let ok = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0;
+        if ok && size.ws_col > 0 {
+            return Some(size.ws_col as usize);
+        }
+    }
+
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|&n: &usize| n > 0)
+}
+
+/// Column count assumed when the terminal width cannot be determined.
+pub const FALLBACK_COLUMNS: usize = 80;
+
+/// A `--width` value: a column count, `auto`, or relative to the terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WidthSpec {
+    /// A fixed number of columns (e.g. `40`)
+    Fixed(usize),
+
+    /// The terminal width (`auto`)
+    Auto,
+
+    /// The terminal width minus N columns (e.g. `-4`)
+    Relative(usize),
+}
+
+impl FromStr for WidthSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || "expected a column count, auto, or -N (terminal width minus N)".to_string();
+        if s == "auto" {
+            Ok(WidthSpec::Auto)
+        } else if let Some(n) = s.strip_prefix('-') {
+            n.parse().map(WidthSpec::Relative).map_err(|_| invalid())
+        } else {
+            s.parse().map(WidthSpec::Fixed).map_err(|_| invalid())
+        }
+    }
+}
+
+impl WidthSpec {
+    /// Resolves the spec to a column count.
+    ///
+    /// The terminal width comes from [`terminal_columns`], falling back to
+    /// [`FALLBACK_COLUMNS`]. Relative widths never drop below one column.
+    pub fn columns(self) -> usize {
+        let terminal = || terminal_columns().unwrap_or(FALLBACK_COLUMNS);
+        match self {
+            WidthSpec::Fixed(n) => n,
+            WidthSpec::Auto => terminal(),
+            WidthSpec::Relative(n) => terminal().saturating_sub(n).max(1),
+        }
+    }
 }
@@ -0,0 +1,185 @@
+//! Color themes for highlighting graphemes by layout role.
+//!
+//! A theme maps each [`Role`] (wide graphemes, zero-width atoms, ZWJ joiners,
+//! modifiers, control characters, out-of-range slice markers) to an SGR
+//! style. It is read from the `[theme]` section of the user config:
+//!
+//! ```text
+//! [theme]
+//! preset = default        # or: none
+//! wide = cyan
+//! zwj = bold magenta
+//! control = white on red
+//! out-of-range = 38;5;208 # raw SGR parameters also work
+//! ```
+//!
+//! Styles are words (`bold`, `dim`, `italic`, `underline`, `reverse`, a color
+//! such as `red` or `bright-blue`, `on <color>` for the background), raw SGR
+//! parameters, or `none`. Like all styling, themes only apply when color is
+//! enabled (see `--color`).
+
+use crate::style::term::paint;
+
+/// Layout role of a highlighted grapheme or marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Graphemes two or more columns wide
+    Wide,
+
+    /// Zero-width atoms (variation selectors, combining marks)
+    ZeroWidth,
+
+    /// Zero Width Joiners and ZWJ sequences
+    Zwj,
+
+    /// Skin-tone and hair modifiers
+    Modifier,
+
+    /// Control characters
+    Control,
+
+    /// Slice bounds past the end of the input
+    OutOfRange,
+}
+
+impl Role {
+    /// All roles, in config-key order.
+    const ALL: [Role; 6] = [
+        Role::Wide,
+        Role::ZeroWidth,
+        Role::Zwj,
+        Role::Modifier,
+        Role::Control,
+        Role::OutOfRange,
+    ];
+
+    /// Config key of the role (e.g. `zero-width`).
+    fn key(self) -> &'static str {
+        match self {
+            Role::Wide => "wide",
+            Role::ZeroWidth => "zero-width",
+            Role::Zwj => "zwj",
+            Role::Modifier => "modifier",
+            Role::Control => "control",
+            Role::OutOfRange => "out-of-range",
+        }
+    }
+}
+
+/// SGR style per role; an empty string leaves the text plain.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    styles: [String; 6],
+}
+
+impl Default for Theme {
+    /// The built-in theme.
+    fn default() -> Self {
+        Theme {
+            styles: ["36", "35", "1;35", "33", "1;31", "30;43"].map(String::from),
+        }
+    }
+}
+
+impl Theme {
+    /// A theme that leaves everything plain.
+    pub fn none() -> Self {
+        Theme {
+            styles: Default::default(),
+        }
+    }
+
+    /// Builds a theme from `[theme]` config entries.
+    ///
+    /// Starts from `preset` (`default` unless set) and applies the per-role
+    /// styles on top. Invalid entries are skipped with a warning.
+    pub fn from_entries(entries: &[(String, String)]) -> Self {
+        let warn = |key: &str, value: &str| {
+            eprintln!("⚠ Ignoring invalid entry '{key} = {value}' in theme.");
+        };
+
+        let mut theme = match entries.iter().find(|(k, _)| k == "preset") {
+            Some((_, v)) if v == "none" => Theme::none(),
+            Some((k, v)) if v != "default" => {
+                warn(k, v);
+                Theme::default()
+            }
+            _ => Theme::default(),
+        };
+
+        for (key, value) in entries {
+            if key == "preset" {
+                continue;
+            }
+            match (
+                Role::ALL.iter().position(|r| r.key() == key),
+                parse_style(value),
+            ) {
+                (Some(i), Some(sgr)) => theme.styles[i] = sgr,
+                _ => warn(key, value),
+            }
+        }
+
+        theme
+    }
+
+    /// Paints `s` in the style of `role`.
+    pub fn paint(&self, role: Role, s: &str) -> String {
+        let style = &self.styles[role as usize];
+        if style.is_empty() {
+            s.to_string()
+        } else {
+            paint(s, style)
+        }
+    }
+
+    /// Paints `s` in the style of `role`, if any.
+    pub fn paint_opt(&self, role: Option<Role>, s: &str) -> String {
+        match role {
+            Some(role) => self.paint(role, s),
+            None => s.to_string(),
+        }
+    }
+}
+
+/// Returns the SGR offset of a color name (`red` → 1, `bright-red` → 61).
+fn color_code(name: &str) -> Option<u8> {
+    const COLORS: [&str; 8] = [
+        "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+    ];
+    let (bright, base) = match name.strip_prefix("bright-") {
+        Some(base) => (true, base),
+        None => (false, name),
+    };
+    let index = COLORS.iter().position(|&c| c == base)? as u8;
+    Some(if bright { 60 + index } else { index })
+}
+
+/// Parses a style such as `bold magenta`, `white on red` or `1;35` into SGR
+/// parameters. `none` yields an empty style.
+fn parse_style(spec: &str) -> Option<String> {
+    let spec = spec.trim();
+    if spec == "none" {
+        return Some(String::new());
+    }
+    if !spec.is_empty() && spec.split(';').all(|p| p.parse::<u8>().is_ok()) {
+        return Some(spec.to_string());
+    }
+
+    let mut codes: Vec<String> = Vec::new();
+    let mut words = spec.split_whitespace();
+    while let Some(word) = words.next() {
+        let code = match word {
+            "bold" => 1,
+            "dim" => 2,
+            "italic" => 3,
+            "underline" => 4,
+            "reverse" => 7,
+            "on" => 40 + color_code(words.next()?)?,
+            color => 30 + color_code(color)?,
+        };
+        codes.push(code.to_string());
+    }
+
+    (!codes.is_empty()).then(|| codes.join(";"))
+}
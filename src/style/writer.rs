@@ -0,0 +1,62 @@
+//! Buffered stdout shared by all commands.
+//!
+//! Commands write to a `&mut dyn Write` instead of calling `println!`, so
+//! they can be run against an in-memory buffer (e.g. `Vec<u8>`) and so a
+//! closed pipe surfaces as an error instead of a panic. [`is_broken_pipe`]
+//! lets `main` treat that error as a clean exit (`runefix ... | head`).
+
+use crate::style::term::is_stdout_terminal;
+use std::io::{self, BufWriter, StdoutLock, Write};
+
+/// Stdout, flushed per line on a terminal and in blocks when piped.
+pub enum StdoutWriter {
+    /// Interactive: stdout's own line buffering, so output appears as it's printed
+    Terminal(StdoutLock<'static>),
+
+    /// Piped or redirected: block-buffered until flushed or dropped
+    Piped(BufWriter<StdoutLock<'static>>),
+}
+
+impl StdoutWriter {
+    /// Locks stdout, choosing the buffering from whether it is a terminal.
+    pub fn new() -> Self {
+        let stdout = io::stdout().lock();
+        if is_stdout_terminal() {
+            StdoutWriter::Terminal(stdout)
+        } else {
+            StdoutWriter::Piped(BufWriter::new(stdout))
+        }
+    }
+}
+
+impl Write for StdoutWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            StdoutWriter::Terminal(w) => w.write(buf),
+            StdoutWriter::Piped(w) => w.write(buf),
+        }
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match self {
+            StdoutWriter::Terminal(w) => w.write_all(buf),
+            StdoutWriter::Piped(w) => w.write_all(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            StdoutWriter::Terminal(w) => w.flush(),
+            StdoutWriter::Piped(w) => w.flush(),
+        }
+    }
+}
+
+/// Returns `true` if `err` was caused by writing to a closed pipe (`EPIPE`).
+pub fn is_broken_pipe(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<io::Error>()
+            .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe)
+    })
+}